@@ -0,0 +1,198 @@
+use std::fs::{create_dir_all, File};
+use std::io::{LineWriter, Write};
+use std::path::Path;
+
+use quick_xml::escape::partial_escape;
+
+use crate::placeholders::{parse_placeholders, replace_placeholders_escaped, Placeholder};
+use crate::strings_generator::{unique_names, Generator, TranslationOut};
+
+const JAVA_KEYWORDS: [&str; 50] = [
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const", "continue",
+    "default", "do", "double", "else", "enum", "extends", "final", "finally", "float", "for", "goto", "if",
+    "implements", "import", "instanceof", "int", "interface", "long", "native", "new", "package", "private",
+    "protected", "public", "return", "short", "static", "strictfp", "super", "switch", "synchronized", "this",
+    "throw", "throws", "transient", "try", "void", "volatile", "while"
+];
+
+/// Android string resources of a language, written to `values-<qualifier>/strings.xml` or to
+/// `values/strings.xml` when `lang` is `None`. Plural keys are `plurals` resources, placeholders are
+/// written as positional Java format specifiers like `%1$s`.
+pub struct TranslationsAndroid {
+    pub lang: Option<String>,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsAndroid {
+    fn generate(&self) -> std::io::Result<()> {
+        let document = self.document()?;
+
+        let dir = match &self.lang {
+            Some(lang) => format!("values-{}", android_qualifier(lang)),
+            None => "values".to_string()
+        };
+        create_dir_all(&dir)?;
+
+        let file = File::create(Path::new(&dir).join("strings.xml"))?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(document.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsAndroid {
+    /// Resources document. Keys which map to the same resource name are an error.
+    fn document(&self) -> std::io::Result<String> {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+        let translations = self.translations.iter().filter(|t| !t.missing);
+
+        for (name, translation) in unique_names(translations, "strings.xml", android_name)? {
+            if let Some(comment) = &translation.comment {
+                out.push_str(&format!("    <!-- {} -->\n", comment.replace("--", "- -")));
+            }
+
+            if translation.plurals.is_empty() {
+                out.push_str(&format!("    <string name=\"{}\">{}</string>\n", name,
+                                      android_value(&translation.value)));
+                continue;
+            }
+
+            out.push_str(&format!("    <plurals name=\"{}\">\n", name));
+            for (category, value) in &translation.plurals {
+                out.push_str(&format!("        <item quantity=\"{}\">{}</item>\n", category, android_value(value)));
+            }
+            if !translation.plurals.contains_key("other") {
+                out.push_str(&format!("        <item quantity=\"other\">{}</item>\n", android_value(&translation.value)));
+            }
+            out.push_str("    </plurals>\n");
+        }
+
+        out.push_str("</resources>\n");
+        Ok(out)
+    }
+}
+
+/// Converts key to resource name, e.g. `app.title` to `app_title`. Names have to be valid Java
+/// identifiers, so `key_` is prepended to names starting with a digit and `_` appended to keywords.
+pub fn android_name(key: &str) -> String {
+    let mut name: String = key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name = format!("key_{}", name);
+    }
+    if JAVA_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+
+    name
+}
+
+/// Resource qualifier of the language, e.g. `pl-rPL` for `pl-PL` or `b+zh+Hant+TW` for languages
+/// with script.
+fn android_qualifier(lang: &str) -> String {
+    let parts: Vec<&str> = lang.split(['-', '_'].as_ref()).collect();
+
+    match parts.as_slice() {
+        [language] => language.to_string(),
+        [language, region] if region.len() == 2 || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit())) =>
+            format!("{}-r{}", language, region.to_uppercase()),
+        _ => format!("b+{}", parts.join("+"))
+    }
+}
+
+/// Escapes value for string resource. Values with placeholders are format strings, so literal
+/// percent signs are escaped there.
+fn android_value(value: &str) -> String {
+    let formatted = !parse_placeholders(value).is_empty();
+    let text = replace_placeholders_escaped(value,
+                                            |text| if formatted { text.replace('%', "%%") } else { text.to_string() },
+                                            |p| java_placeholder(value, p));
+
+    let mut escaped = text
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+
+    if escaped.starts_with('@') || escaped.starts_with('?') {
+        escaped.insert(0, '\\');
+    }
+    if escaped.starts_with(' ') || escaped.ends_with(' ') || escaped.contains("  ") {
+        escaped = format!("\"{}\"", escaped);
+    }
+
+    partial_escape(&escaped).to_string()
+}
+
+/// Positional Java format specifier of the placeholder, flags, width and precision are kept.
+fn java_placeholder(value: &str, placeholder: &Placeholder) -> String {
+    let spec = &value[placeholder.range.start + 1..placeholder.range.end];
+    let spec = match spec.find('$') {
+        Some(idx) if spec[..idx].chars().all(|c| c.is_ascii_digit()) => &spec[idx + 1..],
+        _ => spec
+    };
+
+    let mut out = format!("%{}$", placeholder.index);
+    let (body, conversion) = spec.split_at(spec.len() - 1);
+    out.extend(body.chars().filter(|c| !"hlqzjt".contains(*c)));
+    out.push_str(match conversion {
+        "@" | "c" | "C" | "S" => "s",
+        "i" | "u" => "d",
+        other => other
+    });
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::android_generator::{android_name, android_qualifier, android_value, TranslationsAndroid};
+    use crate::strings_generator::TranslationOut;
+
+    #[test]
+    fn convert_keys_and_langs() {
+        assert_eq!("app_main_title", android_name("app.main-title"));
+        assert_eq!("key_1st", android_name("1st"));
+        assert_eq!("class_", android_name("class"));
+        assert_eq!("pl-rPL", android_qualifier("pl-PL"));
+        assert_eq!("es-r419", android_qualifier("es-419"));
+        assert_eq!("b+zh+Hant+TW", android_qualifier("zh-Hant-TW"));
+        assert_eq!("de", android_qualifier("de"));
+    }
+
+    #[test]
+    fn escape_android_values() {
+        assert_eq!("Hello %1$s, it\\'s %2$.1f%%", android_value("Hello %@, it's %.1lf%%"));
+        assert_eq!("%2$d of %1$s", android_value("%2$ld of %1$s"));
+        assert_eq!("100% &lt;b&gt;\\\"done\\\"\\n", android_value("100%% <b>\"done\"\n"));
+        assert_eq!("\\@home", android_value("@home"));
+        assert_eq!("\" padded \"", android_value(" padded "));
+    }
+
+    #[test]
+    fn generate_strings_xml() {
+        let mut hello = TranslationOut::new("app.hello", "Hello %s");
+        hello.comment = Some("Greeting -- short".to_string());
+        let mut files = TranslationOut::new("app.files", "%d files");
+        files.plurals.insert("one".to_string(), "%d file".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsAndroid { lang: None, translations: vec![hello, files, missing] };
+
+        assert_eq!("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n    \
+                        <!-- Greeting - - short -->\n    \
+                        <string name=\"app_hello\">Hello %1$s</string>\n    \
+                        <plurals name=\"app_files\">\n        \
+                            <item quantity=\"one\">%1$d file</item>\n        \
+                            <item quantity=\"other\">%1$d files</item>\n    \
+                        </plurals>\n\
+                    </resources>\n", generator.document().unwrap());
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::android_generator::android_name;
+use crate::encoding::collect_paths;
+use crate::excel_reader::{ImportResult, Importer};
+use crate::json_data::{DataRootTranslations, Project};
+use crate::placeholders::parse_placeholders;
+use crate::strings_reader::matching_lang;
+use crate::xml::attribute;
+
+#[derive(Debug)]
+pub enum AndroidError {
+    Io(std::io::Error),
+    Xml(String, quick_xml::Error)
+}
+
+impl fmt::Display for AndroidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AndroidError::Io(e) => write!(f, "cannot read resources file: {}", e),
+            AndroidError::Xml(name, e) => write!(f, "invalid resources file \"{}\": {}", name, e)
+        }
+    }
+}
+
+impl Error for AndroidError {}
+
+impl From<std::io::Error> for AndroidError {
+    fn from(e: std::io::Error) -> Self {
+        AndroidError::Io(e)
+    }
+}
+
+type Result<T> = std::result::Result<T, AndroidError>;
+
+/// Resources file of `values` directory with its project language.
+pub struct AndroidFile {
+    pub name: String,
+    pub lang: String,
+    pub data: String
+}
+
+#[derive(Debug, PartialEq)]
+enum Resource {
+    String { name: String, value: String, translatable: bool },
+    Plurals { name: String, items: Vec<(String, String)> },
+    Array { name: String, items: Vec<String> }
+}
+
+struct Entry {
+    resource: Resource,
+    comment: Option<String>
+}
+
+/// Reads resources files of project languages from `values` directories, `path` is either
+/// a directory searched recursively or a single file. Directories with qualifiers other than
+/// language and region, like `values-night`, are skipped, as are languages not in project.
+pub fn read_android_files(path: &Path, project: &Project) -> Result<Vec<AndroidFile>> {
+    let mut paths = vec![];
+    collect_paths(path, "xml", &mut paths)?;
+    paths.sort();
+
+    let mut files = vec![];
+    for path in paths {
+        let lang = path.parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|dir| dir.to_str())
+            .and_then(|dir| values_lang(dir, project));
+
+        if let Some(lang) = lang {
+            files.push(AndroidFile { name: path.display().to_string(), lang, data: fs::read_to_string(&path)? });
+        }
+    }
+
+    Ok(files)
+}
+
+/// Imports Android resources into project, default language files first. Resource names are
+/// matched to keys exported with them, e.g. `app_title` to `app.title`, unknown names are used
+/// as keys. Array items are imported as `<name>.<index>` keys. Non-translatable strings are
+/// imported from the default language files only, as are comments.
+pub fn import_android(files: &[AndroidFile], data_root: &mut DataRootTranslations, project: &Project,
+                      project_chain: &[u16], ignore_unknown: bool) -> Result<ImportResult> {
+    let keys = resource_keys(data_root, project);
+    let key = |name: &str| keys.get(name).cloned().unwrap_or_else(|| name.to_string());
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
+
+    let mut files: Vec<&AndroidFile> = files.iter().collect();
+    files.sort_by_key(|file| file.lang.ne(&project.default_lang));
+
+    for file in files {
+        let default_lang = file.lang.eq(&project.default_lang);
+        let entries = parse(&file.data).map_err(|e| AndroidError::Xml(file.name.to_string(), e))?;
+
+        for entry in entries {
+            let entry_key = match &entry.resource {
+                Resource::String { name, translatable, .. } if default_lang || *translatable => key(name),
+                Resource::String { .. } => continue,
+                Resource::Plurals { name, .. } => key(name),
+                Resource::Array { name, items } => {
+                    for (idx, item) in items.iter().enumerate() {
+                        importer.value(&format!("{}.{}", key(name), idx), &file.lang, item.to_string());
+                    }
+                    continue;
+                }
+            };
+
+            match entry.resource {
+                Resource::String { value, .. } => importer.value(&entry_key, &file.lang, value),
+                Resource::Plurals { items, .. } => {
+                    for (category, value) in items {
+                        importer.plural(&entry_key, &file.lang, &category, value);
+                    }
+                }
+                Resource::Array { .. } => {}
+            }
+
+            if let Some(comment) = entry.comment.filter(|_| default_lang) {
+                if let Some(translation) = importer.translation(&entry_key) {
+                    translation.comment = Some(comment);
+                }
+            }
+        }
+    }
+
+    Ok(importer.result)
+}
+
+/// Keys by resource name, keys of the project take precedence over other ones.
+fn resource_keys(data_root: &DataRootTranslations, project: &Project) -> BTreeMap<String, String> {
+    let mut keys = BTreeMap::new();
+    let (in_project, other): (Vec<_>, Vec<_>) = data_root.iter()
+        .partition(|(_, translation)| translation.projects.contains(&project.id));
+
+    for (key, _) in in_project.into_iter().chain(other) {
+        keys.entry(android_name(key)).or_insert_with(|| key.to_string());
+    }
+
+    keys
+}
+
+/// Project language of the `values` directory, e.g. `pl-PL` for `values-pl-rPL` or `values-pl`.
+fn values_lang(dir: &str, project: &Project) -> Option<String> {
+    let qualifiers = dir.strip_prefix("values")?;
+    if qualifiers.is_empty() {
+        return Some(project.default_lang.to_string());
+    }
+
+    let qualifiers = qualifiers.strip_prefix('-')?;
+    let code = match qualifiers.strip_prefix("b+") {
+        Some(tag) => tag.replace('+', "-"),
+        None => {
+            let mut parts = qualifiers.split('-');
+            let language = parts.next().filter(|l| (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_lowercase()))?;
+            match (parts.next(), parts.next()) {
+                (None, _) => language.to_string(),
+                (Some(region), None) => format!("{}-{}", language, region.strip_prefix('r')?),
+                _ => return None
+            }
+        }
+    };
+
+    matching_lang(&code, project)
+}
+
+/// Parses resources with their preceding comments.
+fn parse(data: &str) -> std::result::Result<Vec<Entry>, quick_xml::Error> {
+    let mut reader = Reader::from_str(data);
+    let mut entries = vec![];
+    let mut comment: Option<String> = None;
+
+    let mut current: Option<(Resource, Option<String>)> = None;
+    let mut text: Option<AndroidText> = None;
+    let mut depth = 0;
+
+    loop {
+        match reader.read_event()? {
+            Event::Comment(e) if depth == 1 => {
+                comment = Some(String::from_utf8_lossy(&e).trim().to_string());
+            }
+            Event::Start(e) => {
+                depth += 1;
+                match (depth, &mut current, e.local_name().as_ref()) {
+                    (2, None, b"string") => {
+                        let resource = Resource::String {
+                            name: attribute(&e, "name")?.unwrap_or_default(),
+                            value: String::new(),
+                            translatable: attribute(&e, "translatable")?.as_deref() != Some("false")
+                        };
+                        text = Some(AndroidText::new(attribute(&e, "formatted")?.as_deref() != Some("false")));
+                        current = Some((resource, comment.take()));
+                    }
+                    (2, None, b"plurals") => {
+                        let name = attribute(&e, "name")?.unwrap_or_default();
+                        current = Some((Resource::Plurals { name, items: vec![] }, comment.take()));
+                    }
+                    (2, None, b"string-array") => {
+                        let name = attribute(&e, "name")?.unwrap_or_default();
+                        current = Some((Resource::Array { name, items: vec![] }, comment.take()));
+                    }
+                    (3, Some((Resource::Plurals { items, .. }, _)), b"item") => {
+                        items.push((attribute(&e, "quantity")?.unwrap_or_default(), String::new()));
+                        text = Some(AndroidText::new(true));
+                    }
+                    (3, Some((Resource::Array { .. }, _)), b"item") => text = Some(AndroidText::new(true)),
+                    (_, _, name) => {
+                        if let Some(text) = text.as_mut().filter(|_| name.ne(b"g")) {
+                            text.push_markup(&format!("<{}>", String::from_utf8_lossy(&e)));
+                        }
+                    }
+                }
+            }
+            Event::Empty(e) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_markup(&format!("<{}/>", String::from_utf8_lossy(&e)));
+                }
+            }
+            Event::Text(e) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_text(&e.unescape()?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_text(&String::from_utf8_lossy(&e.into_inner()));
+                }
+            }
+            Event::End(e) => {
+                depth -= 1;
+                match (depth, &mut current) {
+                    (1, Some(_)) => {
+                        if let Some((mut resource, comment)) = current.take() {
+                            if let (Resource::String { value, .. }, Some(text)) = (&mut resource, text.take()) {
+                                *value = text.finish();
+                            }
+                            entries.push(Entry { resource, comment });
+                        }
+                    }
+                    (2, Some((Resource::Plurals { items, .. }, _))) => {
+                        if let (Some(item), Some(value)) = (items.last_mut(), text.take()) {
+                            item.1 = value.finish();
+                        }
+                    }
+                    (2, Some((Resource::Array { items, .. }, _))) => {
+                        if let Some(value) = text.take() {
+                            items.push(value.finish());
+                        }
+                    }
+                    _ => {
+                        let name = e.local_name();
+                        if let Some(text) = text.as_mut().filter(|_| name.as_ref().ne(b"g")) {
+                            text.push_markup(&format!("</{}>", String::from_utf8_lossy(e.name().as_ref())));
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Value text with Android escapes resolved. Whitespace outside of double quotes is collapsed,
+/// markup is kept as it is.
+struct AndroidText {
+    out: String,
+    formatted: bool,
+    in_quotes: bool,
+    pending_space: bool,
+    escape: bool
+}
+
+impl AndroidText {
+    fn new(formatted: bool) -> AndroidText {
+        AndroidText { out: String::new(), formatted, in_quotes: false, pending_space: false, escape: false }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut chars = text.chars();
+
+        while let Some(c) = chars.next() {
+            if self.escape {
+                self.escape = false;
+                let unescaped = match c {
+                    'n' => '\n',
+                    't' => '\t',
+                    'u' => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32).unwrap_or('\u{fffd}')
+                    }
+                    other => other
+                };
+                self.push_char(unescaped);
+                continue;
+            }
+
+            match c {
+                '\\' => self.escape = true,
+                '"' => self.in_quotes = !self.in_quotes,
+                c if c.is_whitespace() && !self.in_quotes => self.pending_space = true,
+                c => self.push_char(c)
+            }
+        }
+    }
+
+    fn push_markup(&mut self, markup: &str) {
+        self.flush_space();
+        self.out.push_str(markup);
+    }
+
+    fn push_char(&mut self, c: char) {
+        self.flush_space();
+        self.out.push(c);
+    }
+
+    fn flush_space(&mut self) {
+        if self.pending_space && !self.out.is_empty() {
+            self.out.push(' ');
+        }
+        self.pending_space = false;
+    }
+
+    /// Value in the canonical form, positional placeholders in order become sequential ones and
+    /// percent signs of not formatted strings are escaped.
+    fn finish(self) -> String {
+        if !self.formatted {
+            return self.out.replace('%', "%%");
+        }
+
+        let placeholders = parse_placeholders(&self.out);
+        let in_order = placeholders.iter().enumerate().all(|(idx, p)| {
+            let spec = &self.out[p.range.clone()];
+            spec.starts_with(&format!("%{}$", idx + 1))
+        });
+        if !in_order {
+            return self.out;
+        }
+
+        let mut out = String::new();
+        let mut last = 0;
+        for placeholder in placeholders {
+            out.push_str(&self.out[last..placeholder.range.start]);
+            let spec = &self.out[placeholder.range.clone()];
+            out.push('%');
+            out.push_str(&spec[spec.find('$').map(|idx| idx + 1).unwrap_or(1)..]);
+            last = placeholder.range.end;
+        }
+        out.push_str(&self.out[last..]);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::android_reader::{parse, Resource};
+
+    fn resources(data: &str) -> Vec<Resource> {
+        parse(data).unwrap().into_iter().map(|e| e.resource).collect()
+    }
+
+    #[test]
+    fn parse_resources() {
+        let entries = parse("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n    <!-- Greeting -->\n    \
+                             <string name=\"app_hello\">Hello <b>%1$s</b>,\n  it\\'s \"  me \"\\n\\u00e9 &amp; %2$d%%</string>\n    \
+                             <string name=\"app_id\" translatable=\"false\">id</string>\n</resources>").unwrap();
+
+        assert_eq!(Some("Greeting".to_string()), entries[0].comment);
+        assert_eq!(Resource::String {
+            name: "app_hello".to_string(),
+            value: "Hello <b>%s</b>, it's   me \né & %d%%".to_string(),
+            translatable: true
+        }, entries[0].resource);
+        assert_eq!(None, entries[1].comment);
+        assert_eq!(Resource::String { name: "app_id".to_string(), value: "id".to_string(), translatable: false },
+                   entries[1].resource);
+    }
+
+    #[test]
+    fn parse_plurals_and_arrays() {
+        let entries = resources("<resources>\n<plurals name=\"files\">\n    <item quantity=\"one\">%1$d file</item>\n    \
+                                 <item quantity=\"other\"><xliff:g id=\"count\">%1$d</xliff:g> files</item>\n</plurals>\n\
+                                 <string-array name=\"days\"><item>Mon</item><item>\"Tue \"</item></string-array>\n\
+                                 <string name=\"raw\" formatted=\"false\">100% %s</string>\n\
+                                 <string name=\"swapped\">%2$s %1$s</string>\n</resources>");
+
+        assert_eq!(vec![
+            Resource::Plurals {
+                name: "files".to_string(),
+                items: vec![("one".to_string(), "%d file".to_string()), ("other".to_string(), "%d files".to_string())]
+            },
+            Resource::Array { name: "days".to_string(), items: vec!["Mon".to_string(), "Tue ".to_string()] },
+            Resource::String { name: "raw".to_string(), value: "100%% %%s".to_string(), translatable: true },
+            Resource::String { name: "swapped".to_string(), value: "%2$s %1$s".to_string(), translatable: true }
+        ], entries);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use serde_json::{json, Map, Value};
+
+use crate::placeholders::{argument_name, count_index, parse_placeholders, replace_placeholders, PlaceholderType,
+                          COUNT_ARGUMENT};
+use crate::strings_generator::{unique_names, Generator, TranslationOut};
+
+const DART_KEYWORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else", "enum",
+    "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null", "rethrow", "return",
+    "super", "switch", "this", "throw", "true", "try", "var", "void", "while", "with"
+];
+
+/// Flutter Application Resource Bundle of a language. Values use ICU message syntax, placeholders
+/// are named `arg1`, `arg2`, ..., the plural count is named `count`.
+pub struct TranslationsArb {
+    pub lang: String,
+    /// Adds `@key` descriptions and placeholder types, needed in the template file only.
+    pub metadata: bool,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsArb {
+    fn generate(&self) -> std::io::Result<()> {
+        let file_name = format!("app_{}.arb", arb_locale(&self.lang));
+        let json = self.bundle()?;
+
+        let file = File::create(file_name)?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(json.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsArb {
+    /// Bundle JSON with every message followed by its metadata. Keys which map to the same Dart
+    /// identifier are an error, as generated getters would clash.
+    fn bundle(&self) -> std::io::Result<String> {
+        let mut entries = vec![("@@locale".to_string(), json!(arb_locale(&self.lang)))];
+        let translations = self.translations.iter().filter(|t| !t.missing);
+
+        for (identifier, translation) in unique_names(translations, "arb file", dart_identifier)? {
+            let (message, placeholders) = TranslationsArb::message(translation);
+            entries.push((identifier.to_string(), json!(message)));
+
+            if self.metadata && (translation.comment.is_some() || !placeholders.is_empty()) {
+                entries.push((format!("@{}", identifier), TranslationsArb::metadata(translation, &placeholders)));
+            }
+        }
+
+        let lines: Vec<String> = entries.iter()
+            .map(|(key, value)| {
+                serde_json::to_string_pretty(value).map(|value| format!("  {}: {}", json!(key), indent(&value)))
+            })
+            .collect::<serde_json::Result<_>>()?;
+
+        Ok(format!("{{\n{}\n}}\n", lines.join(",\n")))
+    }
+
+    /// ICU message of the translation and types of its placeholders by name.
+    fn message(translation: &TranslationOut) -> (String, BTreeMap<String, PlaceholderType>) {
+        let mut placeholders = BTreeMap::new();
+
+        if translation.plurals.is_empty() {
+            let message = TranslationsArb::format(&translation.value, None, &mut placeholders);
+            return (message, placeholders);
+        }
+
+        let count_index = count_index(&translation.value);
+        placeholders.insert(COUNT_ARGUMENT.to_string(), PlaceholderType::Int);
+
+        let forms: Vec<String> = translation.plurals.iter()
+            .map(|(category, value)| format!("{}{{{}}}", category,
+                                             TranslationsArb::format(value, count_index, &mut placeholders)))
+            .collect();
+
+        (format!("{{{}, plural, {}}}", COUNT_ARGUMENT, forms.join(" ")), placeholders)
+    }
+
+    /// Converts value to ICU message, quotes are doubled and braces quoted, so they are not
+    /// treated as arguments.
+    fn format(value: &str, count_index: Option<usize>, placeholders: &mut BTreeMap<String, PlaceholderType>) -> String {
+        for placeholder in parse_placeholders(value) {
+            placeholders.insert(argument_name(&placeholder, count_index), placeholder.kind);
+        }
+
+        let quoted = value
+            .replace('\'', "''")
+            .replace('{', "'{'")
+            .replace('}', "'}'");
+
+        replace_placeholders(&quoted, |p| format!("{{{}}}", argument_name(p, count_index)))
+    }
+
+    fn metadata(translation: &TranslationOut, placeholders: &BTreeMap<String, PlaceholderType>) -> Value {
+        let mut metadata = Map::new();
+
+        if let Some(comment) = &translation.comment {
+            metadata.insert("description".to_string(), json!(comment));
+        }
+
+        if !placeholders.is_empty() {
+            let types: Map<String, Value> = placeholders.iter()
+                .map(|(name, kind)| (name.to_string(), json!({ "type": dart_type(*kind) })))
+                .collect();
+            metadata.insert("placeholders".to_string(), Value::Object(types));
+        }
+
+        Value::Object(metadata)
+    }
+}
+
+/// Converts key to lower camel case Dart identifier, e.g. `app.title` to `appTitle`. Identifiers
+/// starting with a digit get `key` prefix, reserved words get `_` suffix.
+pub fn dart_identifier(key: &str) -> String {
+    let mut identifier = String::new();
+
+    for word in key.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        let first = chars.next().unwrap_or_default();
+
+        if identifier.is_empty() {
+            identifier.push(first.to_ascii_lowercase());
+        } else {
+            identifier.push(first.to_ascii_uppercase());
+        }
+        identifier.push_str(chars.as_str());
+    }
+
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier = format!("key{}", identifier);
+    }
+    if DART_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+
+    identifier
+}
+
+/// ARB files use underscore in locale names, e.g. `pl_PL`.
+fn arb_locale(lang: &str) -> String {
+    lang.replace('-', "_")
+}
+
+fn dart_type(kind: PlaceholderType) -> &'static str {
+    match kind {
+        PlaceholderType::Text => "String",
+        PlaceholderType::Int => "int",
+        PlaceholderType::Double => "double"
+    }
+}
+
+fn indent(json: &str) -> String {
+    json.replace('\n', "\n  ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arb_generator::{dart_identifier, TranslationsArb};
+    use crate::strings_generator::TranslationOut;
+
+    #[test]
+    fn convert_keys_to_dart_identifiers() {
+        assert_eq!("appTitle", dart_identifier("app.title"));
+        assert_eq!("settingsDarkMode", dart_identifier("settings.dark_mode"));
+        assert_eq!("key404Title", dart_identifier("404.title"));
+        assert_eq!("class_", dart_identifier("class"));
+    }
+
+    #[test]
+    fn generate_arb() {
+        let mut files = TranslationOut::new("app.files", "%d files in %s");
+        files.plurals.insert("one".to_string(), "%d file in %s".to_string());
+        files.plurals.insert("other".to_string(), "%d files in %s".to_string());
+        let mut hello = TranslationOut::new("app.hello", "Hello %s, %.1f%%");
+        hello.comment = Some("Greeting".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsArb { lang: "en-US".to_string(), metadata: true, translations: vec![files, hello, missing] };
+        let json: serde_json::Value = serde_json::from_str(&generator.bundle().unwrap()).unwrap();
+
+        assert_eq!("en_US", json["@@locale"]);
+        assert_eq!("{count, plural, one{{count} file in {arg2}} other{{count} files in {arg2}}}", json["appFiles"]);
+        assert_eq!("int", json["@appFiles"]["placeholders"]["count"]["type"]);
+        assert_eq!("String", json["@appFiles"]["placeholders"]["arg2"]["type"]);
+        assert_eq!("Hello {arg1}, {arg2}%", json["appHello"]);
+        assert_eq!("Greeting", json["@appHello"]["description"]);
+        assert_eq!("double", json["@appHello"]["placeholders"]["arg2"]["type"]);
+        assert!(json["appMissing"].is_null());
+    }
+
+    #[test]
+    fn generate_arb_with_quoted_text() {
+        let generator = TranslationsArb {
+            lang: "en-US".to_string(),
+            metadata: false,
+            translations: vec![TranslationOut::new("app.set", "Set {a, b} of %s's")]
+        };
+        let json: serde_json::Value = serde_json::from_str(&generator.bundle().unwrap()).unwrap();
+
+        assert_eq!("Set '{'a, b'}' of {arg1}''s", json["appSet"]);
+    }
+}
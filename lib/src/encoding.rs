@@ -0,0 +1,57 @@
+//! Text encodings and directory trees of imported files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Decodes file contents, UTF-16 is detected by byte order mark or by zero bytes, UTF-8 is the
+/// default.
+pub fn decode(data: &[u8]) -> Option<String> {
+    let utf16 = |data: &[u8], little_endian: bool| {
+        let units: Vec<u16> = data.chunks(2)
+            .map(|c| {
+                let bytes = [c[0], *c.get(1)?];
+                Some(if little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+            })
+            .collect::<Option<_>>()?;
+        String::from_utf16(&units).ok()
+    };
+
+    match data {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).ok(),
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, true),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, false),
+        [0, _, ..] => utf16(data, false),
+        [_, 0, ..] => utf16(data, true),
+        _ => String::from_utf8(data.to_vec()).ok()
+    }
+}
+
+/// Collects files with the extension, `path` is either a directory searched recursively or
+/// a single file.
+pub(crate) fn collect_paths(path: &Path, extension: &str, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect_paths(&entry?.path(), extension, paths)?;
+        }
+    } else if path.extension().is_some_and(|e| e.eq(extension)) {
+        paths.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encoding::decode;
+
+    #[test]
+    fn decode_encodings() {
+        let utf16: Vec<u8> = [0xFF, 0xFE].iter().copied()
+            .chain("\"a\" = \"ż\";".encode_utf16().flat_map(|u| u.to_le_bytes()))
+            .collect();
+
+        assert_eq!(Some("\"a\" = \"ż\";".to_string()), decode(&utf16));
+        assert_eq!(Some("\"a\"".to_string()), decode(&[0, b'"', 0, b'a', 0, b'"']));
+        assert_eq!(Some("\"ż\"".to_string()), decode("\u{feff}\"ż\"".as_bytes()));
+    }
+}
@@ -1,17 +1,22 @@
-use calamine::{Xlsx, Reader, XlsxError};
-use std::io::BufReader;
+use calamine::{Error, Reader, Sheets, open_workbook, open_workbook_auto};
+use std::io::{ErrorKind, Read};
 use std::fs::File;
+use std::path::Path;
+
+use crate::encoding::decode;
 
 pub trait EFile {
     fn rows(&mut self) -> Vec<Vec<String>>;
     fn columns(&self) -> Vec<String>;
 }
 
+/// Workbook in xlsx, xls, xlsb or ods format, only the first worksheet is read.
 pub struct ExcelFile {
-    workbook: Xlsx<BufReader<File>>
+    workbook: Sheets
 }
 
 impl EFile for ExcelFile {
+    #[allow(clippy::needless_range_loop, clippy::needless_return)]
     fn rows(&mut self) -> Vec<Vec<String>> {
         let worksheet = self.workbook.worksheets()
             .first()
@@ -38,9 +43,106 @@ impl EFile for ExcelFile {
 }
 
 impl ExcelFile {
-    pub fn new(file_name: &str) -> Result<ExcelFile, XlsxError> {
-        let workbook = calamine::open_workbook(file_name)?;
+    /// Opens workbook in format detected from its content, files which are not recognized are
+    /// opened according to their extension.
+    pub fn new(file_name: &str) -> Result<ExcelFile, Error> {
+        let workbook = match workbook_format(file_name) {
+            Some(WorkbookFormat::Xls) => Sheets::Xls(open_workbook(file_name).map_err(Error::Xls)?),
+            Some(WorkbookFormat::Xlsx) => Sheets::Xlsx(open_workbook(file_name).map_err(Error::Xlsx)?),
+            Some(WorkbookFormat::Xlsb) => Sheets::Xlsb(open_workbook(file_name).map_err(Error::Xlsb)?),
+            Some(WorkbookFormat::Ods) => Sheets::Ods(open_workbook(file_name).map_err(Error::Ods)?),
+            None => open_workbook_auto(file_name)?
+        };
+
         let excel_file = ExcelFile { workbook };
         Ok(excel_file)
     }
-}
\ No newline at end of file
+}
+
+enum WorkbookFormat {
+    Xls,
+    Xlsx,
+    Xlsb,
+    Ods
+}
+
+/// Format from the file signature, xls is a compound document and the other formats are zip
+/// archives told apart by their entries.
+fn workbook_format(file_name: &str) -> Option<WorkbookFormat> {
+    let mut file = File::open(file_name).ok()?;
+    let mut signature = [0u8; 4];
+    file.read_exact(&mut signature).ok()?;
+
+    match signature {
+        [0xD0, 0xCF, 0x11, 0xE0] => Some(WorkbookFormat::Xls),
+        [b'P', b'K', 3, 4] => {
+            let archive = zip::ZipArchive::new(file).ok()?;
+            let contains = |entry: &str| archive.file_names().any(|name| name.eq(entry));
+
+            if contains("content.xml") {
+                Some(WorkbookFormat::Ods)
+            } else if contains("xl/workbook.bin") {
+                Some(WorkbookFormat::Xlsb)
+            } else if contains("xl/workbook.xml") {
+                Some(WorkbookFormat::Xlsx)
+            } else {
+                None
+            }
+        }
+        _ => None
+    }
+}
+
+/// Comma or tab separated values file, quoted as described by RFC 4180. The text may be UTF-8 or
+/// UTF-16, as written by spreadsheet applications, with an optional BOM.
+pub struct CsvFile {
+    records: Vec<Vec<String>>
+}
+
+impl EFile for CsvFile {
+    fn rows(&mut self) -> Vec<Vec<String>> {
+        self.records.clone()
+    }
+
+    fn columns(&self) -> Vec<String> {
+        self.records.first().cloned().unwrap_or_default()
+    }
+}
+
+impl CsvFile {
+    /// Reads file with delimiter detected from its extension.
+    pub fn new(file_name: &str) -> Result<CsvFile, csv::Error> {
+        CsvFile::parse(&std::fs::read(file_name)?, csv_delimiter(file_name))
+    }
+
+    pub fn parse(data: &[u8], delimiter: u8) -> Result<CsvFile, csv::Error> {
+        let text = decode(data)
+            .ok_or_else(|| std::io::Error::new(ErrorKind::InvalidData, "file is not UTF-8 or UTF-16 text"))?;
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+
+        let records = reader.records()
+            .map(|record| record.map(|r| r.iter().map(|field| field.to_string()).collect()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(CsvFile { records })
+    }
+}
+
+/// Tab for `tsv` and `tab` files, comma otherwise.
+pub fn csv_delimiter(file_name: &str) -> u8 {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match extension.as_str() {
+        "tsv" | "tab" => b'\t',
+        _ => b','
+    }
+}
@@ -1,14 +1,18 @@
-use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 
 use crate::excel_file::EFile;
+use crate::excel_writer::SHARED_SUFFIX;
 use crate::json_data::{Translation, Project, DataRootTranslations};
 
+#[derive(Default)]
 pub struct ImportResult {
     pub added: Vec<String>,
     pub updated: Vec<String>,
-    pub ignored: Vec<String>
+    pub ignored: Vec<String>,
+    /// Keys with values which could not be imported, like unsupported markup or edited shared
+    /// values.
+    pub skipped: Vec<String>
 }
 
 #[derive(Debug, Clone)]
@@ -23,27 +27,44 @@ impl fmt::Display for InvalidLanguageError {
 impl Error for InvalidLanguageError {}
 
 struct Lang {
-    name: String
+    name: String,
+    column: usize
 }
 
 type Result<T> = std::result::Result<T, InvalidLanguageError>;
 
-pub fn import_excel(file: &mut dyn EFile, data_root: &mut DataRootTranslations, project: &Project, ignore_unknown: bool)
-                    -> Result<ImportResult> {
+/// Imports spreadsheet rows into project, `project_chain` is the project followed by its parents.
+/// Empty cells of languages with a shared column keep following the shared value. Shared values
+/// are not imported, as they are used by all projects of the key, keys with an edited shared value
+/// are reported in `skipped`.
+pub fn import_excel(file: &mut dyn EFile, data_root: &mut DataRootTranslations, project: &Project,
+                    project_chain: &[u16], ignore_unknown: bool) -> Result<ImportResult> {
     let mut lang_list: Vec<Lang> = vec![];
-    let mut result = ImportResult { added: vec![], updated: vec![], ignored: vec![] };
+    let mut shared_list: Vec<Lang> = vec![];
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
 
     for (idx, row) in file.rows().into_iter().enumerate() {
         if 0.eq(&idx) {
-            for column in 1..row.len() {
-                let lang = Lang { name: row[column].to_string() };
+            for (column, name) in row.iter().enumerate().skip(1) {
+                let (name, shared) = match name.strip_suffix(SHARED_SUFFIX) {
+                    Some(name) => (name, true),
+                    None => (name.as_str(), false)
+                };
+                let lang = Lang { name: name.to_string(), column };
 
                 if project.langs.contains(&lang.name) {
-                    lang_list.push(lang);
+                    if shared {
+                        shared_list.push(lang);
+                    } else {
+                        lang_list.push(lang);
+                    }
                 } else if !ignore_unknown {
                     return Err(InvalidLanguageError);
                 }
             }
+
+            // Source values go first, so translations in the same row are stamped with them.
+            lang_list.sort_by_key(|lang| lang.name.ne(&project.default_lang));
             continue;
         }
 
@@ -52,58 +73,111 @@ pub fn import_excel(file: &mut dyn EFile, data_root: &mut DataRootTranslations,
             continue;
         }
 
-        for (idx, lang) in lang_list.iter().enumerate() {
-            let value = row[idx + 1].to_string();
+        for lang in &shared_list {
+            let value = row.get(lang.column).map(|v| v.as_str()).unwrap_or_default();
+            let stored = importer.data_root.get(&key)
+                .and_then(|t| t.shared.get(&lang.name))
+                .map(|v| v.as_str())
+                .unwrap_or_default();
 
-            if data_root.contains_key(&key) {
-                update_key_value(data_root, project.id, &key, &lang.name, &value);
+            if value != stored {
+                add_result(key.to_string(), &mut importer.result.skipped);
+            }
+        }
 
-                if !result.added.contains(&key) && !result.updated.contains(&key) {
-                    result.updated.push(key.to_string());
-                }
-            } else if !ignore_unknown {
-                add_new_key(data_root, project.id, key.to_string(), &lang.name, value);
-                add_result(key.to_string(), &mut result.added);
-            } else {
-                add_result(key.to_string(), &mut result.ignored);
+        for lang in &lang_list {
+            let value = row.get(lang.column)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+
+            if value.is_empty() && shared_list.iter().any(|shared| shared.name.eq(&lang.name)) {
+                continue;
             }
+
+            importer.value(&key, &lang.name, value);
         }
     }
 
-    Ok(result)
+    Ok(importer.result)
 }
 
-fn add_result(key: String, list: &mut Vec<String>) {
-    if !list.contains(&key) {
-        list.push(key);
-    }
+/// Imports values the same way for all file formats. Unknown keys are added, unless
+/// `ignore_unknown` is set, and the changes are recorded in `result`.
+pub(crate) struct Importer<'a> {
+    pub data_root: &'a mut DataRootTranslations,
+    pub project: &'a Project,
+    pub project_chain: &'a [u16],
+    pub ignore_unknown: bool,
+    pub result: ImportResult
 }
 
-fn update_key_value(data_root: &mut DataRootTranslations, project_id: u16, key: &String, lang: &String, value: &String) {
-    let translation_data = data_root.get_mut(key)
-        .unwrap();
+impl<'a> Importer<'a> {
+    pub fn new(data_root: &'a mut DataRootTranslations, project: &'a Project, project_chain: &'a [u16],
+               ignore_unknown: bool) -> Importer<'a> {
+        Importer { data_root, project, project_chain, ignore_unknown, result: ImportResult::default() }
+    }
+
+    pub fn value(&mut self, key: &str, lang: &str, value: String) {
+        let (project, chain) = (self.project, self.project_chain);
 
-    if !translation_data.projects.contains(&project_id) {
-        translation_data.projects.push(project_id);
+        if let Some(translation) = self.translation(key) {
+            translation.set_value(project, chain, lang, value);
+        }
     }
 
-    translation_data.values.entry(project_id)
-        .or_insert(BTreeMap::new());
+    /// Marks imported translation as reviewed by translator, so it is up to date even when the
+    /// text did not change.
+    pub fn confirm(&mut self, key: &str, lang: &str) {
+        let (project, chain) = (self.project, self.project_chain);
 
-    translation_data.values.get_mut(&project_id)
-        .unwrap()
-        .insert(lang.to_string(), value.to_string());
-}
+        if let Some(translation) = self.translation(key) {
+            translation.confirm(project, chain, lang);
+        }
+    }
 
-fn add_new_key(data_root: &mut DataRootTranslations, project_id: u16, key: String, lang: &String, value: String) {
-    println!("Adding new key: {}", key);
+    pub fn plural(&mut self, key: &str, lang: &str, category: &str, value: String) {
+        let (project, chain) = (self.project, self.project_chain);
 
-    let mut values_lang_map = BTreeMap::new();
-    values_lang_map.insert(lang.to_string(), value);
+        if let Some(translation) = self.translation(key) {
+            translation.set_plural(project, chain, lang, category, value);
+        }
+    }
+
+    /// Returns translation to import values to, or `None` when the key should be ignored.
+    pub fn translation(&mut self, key: &str) -> Option<&mut Translation> {
+        if self.data_root.contains_key(key) {
+            if !self.result.added.iter().any(|k| k.eq(key)) {
+                add_result(key.to_string(), &mut self.result.updated);
+            }
+        } else if !self.ignore_unknown {
+            add_new_key(self.data_root, self.project, key.to_string());
+            add_result(key.to_string(), &mut self.result.added);
+        } else {
+            add_result(key.to_string(), &mut self.result.ignored);
+            return None;
+        }
 
-    let mut values_map = BTreeMap::new();
-    values_map.insert(project_id, values_lang_map);
+        self.data_root.get_mut(key)
+    }
+
+    /// Languages of the project, default one first so translations are stamped with the imported
+    /// source values.
+    pub fn langs(&self) -> Vec<String> {
+        let mut langs = self.project.langs.to_vec();
+        langs.sort_by_key(|lang| lang.ne(&self.project.default_lang));
+        langs
+    }
+}
+
+fn add_result(key: String, list: &mut Vec<String>) {
+    if !list.contains(&key) {
+        list.push(key);
+    }
+}
+
+fn add_new_key(data_root: &mut DataRootTranslations, project: &Project, key: String) {
+    println!("Adding new key: {}", key);
 
-    let value_node = Translation { projects: vec![1], values: values_map };
-    data_root.insert(key.to_string(), value_node);
+    let value_node = Translation { projects: vec![project.id], ..Translation::default() };
+    data_root.insert(key, value_node);
 }
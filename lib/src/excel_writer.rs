@@ -1,4 +1,12 @@
+use std::fs::File;
+use std::io::Write;
+
+use quick_xml::escape::escape;
 use simple_excel_writer::*;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::json_data::{DataRoot, Project, inherited_project, project_chain};
 
 pub struct ExcelTranslations {
     langs: Vec<String>,
@@ -32,16 +40,91 @@ impl ExcelTranslations {
         wb.write_sheet(&mut sheet, |sheet_writer| {
             let sw = sheet_writer;
 
-            self.build_headers(lang_count, sw);
-            self.build_values(lang_count, sw);
-
-            Ok(())
+            self.build_headers(lang_count, sw)?;
+            self.build_values(lang_count, sw)
         }).expect("Cannot write Excel file!");
 
         wb.close().expect("Cannot close Excel file!");
     }
 
-    fn build_values(&self, lang_count: usize, sw: &mut SheetWriter) {
+    /// Writes the same columns as the xlsx file separated with `delimiter`. The file starts with
+    /// UTF-8 BOM, so spreadsheet applications do not guess the encoding.
+    pub fn generate_csv(&self, file_name: &str, delimiter: u8) -> csv::Result<()> {
+        let mut file = File::create(file_name)?;
+        file.write_all("\u{feff}".as_bytes())?;
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_writer(file);
+
+        writer.write_record(std::iter::once("Key").chain(self.langs.iter().map(|lang| lang.as_str())))?;
+        for value in &self.values {
+            writer.write_record(std::iter::once(&value.key).chain(value.values.iter().take(self.langs.len())))?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes OpenDocument spreadsheet with the same columns as the xlsx file.
+    pub fn generate_ods(&self, file_name: &str) -> zip::result::ZipResult<()> {
+        let mut zip = ZipWriter::new(File::create(file_name)?);
+
+        // Mime type goes first and uncompressed, so the format is recognized from its signature.
+        zip.start_file("mimetype", FileOptions::default().compression_method(CompressionMethod::Stored))?;
+        zip.write_all(ODS_MIME_TYPE.as_bytes())?;
+
+        zip.start_file("META-INF/manifest.xml", FileOptions::default())?;
+        zip.write_all(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                               <manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" \
+                               manifest:version=\"1.2\">\n \
+                               <manifest:file-entry manifest:full-path=\"/\" manifest:version=\"1.2\" \
+                               manifest:media-type=\"{}\"/>\n \
+                               <manifest:file-entry manifest:full-path=\"content.xml\" manifest:media-type=\"text/xml\"/>\n\
+                               </manifest:manifest>\n", ODS_MIME_TYPE).as_bytes())?;
+
+        zip.start_file("content.xml", FileOptions::default())?;
+        zip.write_all(self.ods_content().as_bytes())?;
+        zip.finish()?;
+
+        Ok(())
+    }
+
+    fn ods_content(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <office:document-content xmlns:office=\"urn:oasis:names:tc:opendocument:xmlns:office:1.0\" \
+            xmlns:style=\"urn:oasis:names:tc:opendocument:xmlns:style:1.0\" \
+            xmlns:table=\"urn:oasis:names:tc:opendocument:xmlns:table:1.0\" \
+            xmlns:text=\"urn:oasis:names:tc:opendocument:xmlns:text:1.0\" office:version=\"1.2\">\n\
+            <office:automatic-styles>\n\
+            <style:style style:name=\"co1\" style:family=\"table-column\">\
+            <style:table-column-properties style:column-width=\"6cm\"/></style:style>\n\
+            <style:style style:name=\"co2\" style:family=\"table-column\">\
+            <style:table-column-properties style:column-width=\"10cm\"/></style:style>\n\
+            </office:automatic-styles>\n\
+            <office:body>\n<office:spreadsheet>\n<table:table table:name=\"Localizations\">\n\
+            <table:table-column table:style-name=\"co1\"/>\n");
+
+        if !self.langs.is_empty() {
+            out.push_str(&format!("<table:table-column table:style-name=\"co2\" table:number-columns-repeated=\"{}\"/>\n",
+                                  self.langs.len()));
+        }
+
+        let headers = std::iter::once("Key").chain(self.langs.iter().map(|lang| lang.as_str()));
+        out.push_str(&ods_row(headers));
+        for value in &self.values {
+            let cells = std::iter::once(value.key.as_str())
+                .chain(value.values.iter().take(self.langs.len()).map(|v| v.as_str()));
+            out.push_str(&ods_row(cells));
+        }
+
+        out.push_str("</table:table>\n</office:spreadsheet>\n</office:body>\n</office:document-content>\n");
+        out
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn build_values(&self, lang_count: usize, sw: &mut SheetWriter) -> std::io::Result<()> {
         for value in &self.values {
             let mut cells: Vec<Cell> = vec![];
             let key_cell = Cell { value: CellValue::String(value.key.to_string()), column_index: 1 };
@@ -54,11 +137,14 @@ impl ExcelTranslations {
 
             let mut row = Row::new();
             row.cells = cells;
-            sw.append_row(row);
+            sw.append_row(row)?;
         }
+
+        Ok(())
     }
 
-    fn build_headers(&self, lang_count: usize, sw: &mut SheetWriter) {
+    #[allow(clippy::needless_range_loop)]
+    fn build_headers(&self, lang_count: usize, sw: &mut SheetWriter) -> std::io::Result<()> {
         let mut cells: Vec<Cell> = vec![];
         let key_cell = Cell { value: CellValue::String("Key".to_string()), column_index: 1 };
         cells.push(key_cell);
@@ -70,7 +156,71 @@ impl ExcelTranslations {
 
         let mut row = Row::new();
         row.cells = cells;
-        sw.append_row(row);
+        sw.append_row(row)
+    }
+}
+
+const ODS_MIME_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+fn ods_row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let mut row = String::from("<table:table-row>");
+
+    for cell in cells {
+        if cell.is_empty() {
+            row.push_str("<table:table-cell/>");
+            continue;
+        }
+
+        // Readers take the exact value from the attribute, paragraphs are for displaying it.
+        let value = escape(cell).replace('\n', "&#10;").replace('\r', "&#13;").replace('\t', "&#9;");
+        row.push_str(&format!("<table:table-cell office:value-type=\"string\" office:string-value=\"{}\">", value));
+        for line in cell.lines() {
+            row.push_str(&format!("<text:p>{}</text:p>", ods_text(line)));
+        }
+        row.push_str("</table:table-cell>");
+    }
+
+    row.push_str("</table:table-row>\n");
+    row
+}
+
+/// Paragraph text with tabs and spaces, which would be collapsed, written as elements.
+fn ods_text(line: &str) -> String {
+    let mut text = String::new();
+    let mut spaces = 0;
+
+    for c in line.chars() {
+        match c {
+            ' ' => spaces += 1,
+            '\t' => {
+                push_ods_spaces(&mut text, spaces, false);
+                spaces = 0;
+                text.push_str("<text:tab/>");
+            }
+            c => {
+                push_ods_spaces(&mut text, spaces, true);
+                spaces = 0;
+                let mut buffer = [0u8; 4];
+                text.push_str(&escape(c.encode_utf8(&mut buffer)));
+            }
+        }
+    }
+    push_ods_spaces(&mut text, spaces, false);
+
+    text
+}
+
+/// Single space between words is kept as it is, other ones are written as `text:s`.
+fn push_ods_spaces(text: &mut String, mut count: usize, before_word: bool) {
+    if count > 0 && before_word && !text.is_empty() && !text.ends_with('>') {
+        text.push(' ');
+        count -= 1;
+    }
+
+    match count {
+        0 => {}
+        1 => text.push_str("<text:s/>"),
+        count => text.push_str(&format!("<text:s text:c=\"{}\"/>", count))
     }
 }
 
@@ -78,4 +228,42 @@ impl ExcelTranslation {
     pub fn new(key: String, values: Vec<String>) -> ExcelTranslation {
         ExcelTranslation { key, values }
     }
-}
\ No newline at end of file
+}
+
+/// Suffix of the header of columns with values shared by all projects of the key.
+pub const SHARED_SUFFIX: &str = " (shared)";
+
+/// Collects project values for export, values inherited from parent projects are exported where
+/// project does not override them. Shared values are written once, in `<lang> (shared)` columns
+/// after the project languages, and project cells stay empty where the key uses the shared value.
+/// With `outdated_only` only keys with at least one outdated translation are exported, the
+/// remaining values are kept as context for the translator.
+pub fn prepare_translations(data_root: &DataRoot, project: &Project, outdated_only: bool) -> ExcelTranslations {
+    let chain = project_chain(&data_root.projects, project);
+    let project = inherited_project(&data_root.projects, project);
+    let translations: Vec<_> = data_root.translations.iter()
+        .filter(|(_, t)| t.in_projects(&chain))
+        .filter(|(_, t)| !outdated_only || !t.outdated_langs(&project, &chain).is_empty())
+        .collect();
+
+    let shared_langs: Vec<&String> = project.langs.iter()
+        .filter(|&lang| translations.iter().any(|(_, t)| t.shared.contains_key(lang)))
+        .collect();
+
+    let mut values: Vec<ExcelTranslation> = vec![];
+    for (key, translation) in translations {
+        let project_values = project.langs.iter()
+            .map(|lang| chain.iter().find_map(|&id| translation.value(id, lang)).cloned().unwrap_or_default());
+        let shared_values = shared_langs.iter()
+            .map(|&lang| translation.shared.get(lang).cloned().unwrap_or_default());
+
+        values.push(ExcelTranslation::new(key.to_string(), project_values.chain(shared_values).collect()));
+    }
+
+    let langs = project.langs.iter()
+        .cloned()
+        .chain(shared_langs.iter().map(|lang| format!("{}{}", lang, SHARED_SUFFIX)))
+        .collect();
+
+    ExcelTranslations::new(langs, values)
+}
@@ -0,0 +1,170 @@
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use crate::placeholders::{argument_name, count_index, replace_placeholders_escaped, COUNT_ARGUMENT};
+use crate::strings_generator::{unique_names, Generator, TranslationOut};
+
+/// Characters with a special meaning at the start of a pattern line.
+const LINE_START_SPECIAL: [char; 4] = [' ', '[', '*', '.'];
+
+/// Project Fluent resource of a language. Keys become message identifiers like `app-title`,
+/// placeholders are written as `{ $arg1 }` and plural keys as select expressions over
+/// `NUMBER($count)` with `other` as the default variant.
+pub struct TranslationsFluent {
+    pub lang: String,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsFluent {
+    fn generate(&self) -> std::io::Result<()> {
+        let resource = self.resource()?;
+
+        let file = File::create(format!("{}.ftl", self.lang))?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(resource.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsFluent {
+    /// Resource with every message preceded by its comment. Keys which map to the same identifier
+    /// are an error.
+    fn resource(&self) -> std::io::Result<String> {
+        let mut out = String::new();
+        let translations = self.translations.iter().filter(|t| !t.missing);
+
+        for (identifier, translation) in unique_names(translations, "ftl file", fluent_identifier)? {
+            if let Some(comment) = &translation.comment {
+                for line in comment.lines() {
+                    out.push_str(format!("# {}", line).trim_end());
+                    out.push('\n');
+                }
+            }
+
+            out.push_str(&format!("{} ={}\n", identifier, TranslationsFluent::value(translation)));
+        }
+
+        Ok(out)
+    }
+
+    /// Message value following `=`, multiline patterns and select expressions start on a new line.
+    fn value(translation: &TranslationOut) -> String {
+        if translation.plurals.is_empty() {
+            let pattern = pattern(&translation.value, None);
+
+            return if pattern.contains('\n') {
+                format!("\n    {}", indent(&pattern, "    "))
+            } else {
+                format!(" {}", pattern)
+            };
+        }
+
+        let count_index = count_index(&translation.value);
+        let mut out = format!("\n    {{ NUMBER(${}) ->\n", COUNT_ARGUMENT);
+
+        for (category, value) in &translation.plurals {
+            if category != "other" {
+                out.push_str(&format!("        [{}] {}\n", category, indent(&pattern(value, count_index), "            ")));
+            }
+        }
+
+        let other = translation.plurals.get("other").unwrap_or(&translation.value);
+        out.push_str(&format!("       *[other] {}\n    }}", indent(&pattern(other, count_index), "            ")));
+        out
+    }
+}
+
+/// Converts key to Fluent identifier, e.g. `app.title` to `app-title`. Identifiers have to start
+/// with a letter, so `key-` is prepended otherwise.
+pub fn fluent_identifier(key: &str) -> String {
+    let identifier: String = key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '-' })
+        .collect();
+
+    if identifier.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        identifier
+    } else {
+        format!("key-{}", identifier)
+    }
+}
+
+/// Pattern of the value, braces and whitespace or special characters at the start and end of
+/// lines are written as string literals.
+fn pattern(value: &str, count_index: Option<usize>) -> String {
+    let text = replace_placeholders_escaped(value, escape_braces,
+                                            |p| format!("{{ ${} }}", argument_name(p, count_index)));
+
+    if text.is_empty() {
+        return "{\"\"}".to_string();
+    }
+
+    let lines: Vec<String> = text.split('\n').map(protect_line).collect();
+    lines.join("\n")
+}
+
+fn escape_braces(text: &str) -> String {
+    text.chars()
+        .map(|c| if c == '{' || c == '}' { format!("{{\"{}\"}}", c) } else { c.to_string() })
+        .collect()
+}
+
+fn protect_line(line: &str) -> String {
+    let mut out = line.to_string();
+
+    if out.ends_with(' ') {
+        out.pop();
+        out.push_str("{\" \"}");
+    }
+    if let Some(first) = out.chars().next().filter(|c| LINE_START_SPECIAL.contains(c)) {
+        out = format!("{{\"{}\"}}{}", first, &out[first.len_utf8()..]);
+    }
+
+    out
+}
+
+fn indent(pattern: &str, indent: &str) -> String {
+    pattern.replace('\n', &format!("\n{}", indent))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fluent_generator::{fluent_identifier, TranslationsFluent};
+    use crate::strings_generator::TranslationOut;
+
+    #[test]
+    fn convert_keys_to_identifiers() {
+        assert_eq!("app-title", fluent_identifier("app.title"));
+        assert_eq!("app-main_title", fluent_identifier("app.main_title"));
+        assert_eq!("key-1-title", fluent_identifier("1.title"));
+    }
+
+    #[test]
+    fn generate_ftl() {
+        let mut hello = TranslationOut::new("app.hello", "Hello {%s}");
+        hello.comment = Some("Greeting".to_string());
+        let mut files = TranslationOut::new("app.files", "%d files in %s");
+        files.plurals.insert("one".to_string(), "%d file in %s".to_string());
+        files.plurals.insert("other".to_string(), "%d files in %s".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsFluent {
+            lang: "en-US".to_string(),
+            translations: vec![hello, files, TranslationOut::new("app.note", "First\n[second] "), missing]
+        };
+
+        assert_eq!("# Greeting\n\
+                    app-hello = Hello {\"{\"}{ $arg1 }{\"}\"}\n\
+                    app-files =\n    \
+                        { NUMBER($count) ->\n        \
+                            [one] { $count } file in { $arg2 }\n       \
+                           *[other] { $count } files in { $arg2 }\n    \
+                        }\n\
+                    app-note =\n    \
+                        First\n    \
+                        {\"[\"}second]{\" \"}\n", generator.resource().unwrap());
+    }
+}
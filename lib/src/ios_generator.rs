@@ -28,7 +28,8 @@ impl Generator for TranslationsIOS {
 }
 
 impl TranslationsIOS {
-    fn escape(translation: &str) -> String {
+    #[allow(clippy::let_and_return)]
+    pub(crate) fn escape(translation: &str) -> String {
         let out_value = translation
             .replace("%s", "%@")
             .replace("%d", "%@")
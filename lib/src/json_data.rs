@@ -12,19 +12,355 @@ pub struct DataRoot {
     pub translations: BTreeMap<String, Translation>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
     pub id: u16,
     pub name: String,
     pub langs: Vec<String>,
-    pub default_lang: String
+    pub default_lang: String,
+    /// Project this one inherits keys, languages and values from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u16>
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct Translation {
     pub projects: Vec<u16>,
-    pub values: BTreeMap<u16, BTreeMap<String, String>>
+    pub values: BTreeMap<u16, BTreeMap<String, String>>,
+    /// Values used by all projects of the key, unless overridden in `values`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shared: BTreeMap<String, String>,
+    /// Fingerprints of the default language value each translation was made from.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<u16, BTreeMap<String, String>>,
+    /// Plural forms by CLDR category (`zero`, `one`, `two`, `few`, `many`, `other`). The `other`
+    /// form is kept in `values` too, for targets without plurals support.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub plurals: BTreeMap<u16, BTreeMap<String, BTreeMap<String, String>>>,
+    /// Description for translators.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>
+}
+
+pub const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+impl Translation {
+    /// Sets value for the language in project, `project_chain` is the project with its parents,
+    /// see [`project_chain`]. Changed translations are stamped with the fingerprint of the current
+    /// default language value, so changing it later marks them as outdated. Setting the same text
+    /// again keeps the translation outdated. Value equal to the one inherited from parents or the
+    /// shared one is not stored in project, so it keeps following them.
+    pub fn set_value(&mut self, project: &Project, project_chain: &[u16], lang: &str, value: String) {
+        if !self.in_projects(project_chain) {
+            self.projects.push(project.id);
+        }
+
+        if lang == project.default_lang {
+            self.stamp_unknown_sources(project, project_chain);
+        }
+
+        let changed = self.resolve_in(project_chain, lang) != Some(&value);
+
+        if self.resolve_in(&project_chain[1..], lang) == Some(&value) {
+            self.remove_override(project.id, lang);
+        } else {
+            self.values.entry(project.id)
+                .or_default()
+                .insert(lang.to_string(), value);
+        }
+
+        if lang == project.default_lang {
+            return;
+        }
+
+        if self.supplier(project_chain, lang).is_some_and(|id| id != project.id) {
+            // Parent translation is checked against the fingerprint recorded in parent.
+            if let Some(sources) = self.sources.get_mut(&project.id) {
+                sources.remove(lang);
+            }
+        } else if changed {
+            self.stamp_source(project, project_chain, lang);
+        }
+    }
+
+    /// Sets value shared by all projects of the key. Projects with the same value stored stop
+    /// overriding it.
+    pub fn set_shared_value(&mut self, projects: &[Project], lang: &str, value: String) {
+        let members: Vec<(&Project, Vec<u16>)> = projects.iter()
+            .filter(|p| self.projects.contains(&p.id))
+            .map(|p| (p, project_chain(projects, p)))
+            .collect();
+
+        for (project, chain) in &members {
+            if lang == project.default_lang {
+                self.stamp_unknown_sources(project, chain);
+            }
+        }
+
+        let changed: Vec<bool> = members.iter()
+            .map(|(p, _)| self.resolve(p.id, lang) != Some(&value))
+            .collect();
+
+        self.shared.insert(lang.to_string(), value.to_string());
+
+        for ((project, chain), changed) in members.into_iter().zip(changed) {
+            if self.value(project.id, lang) == Some(&value) {
+                self.remove_override(project.id, lang);
+            }
+
+            if lang != project.default_lang && changed && self.is_shared(project.id, lang) {
+                self.stamp_source(project, &chain, lang);
+            }
+        }
+    }
+
+    /// Sets plural form for the language in project, `other` form is set as the value too.
+    /// Project forms start as a copy of the inherited ones and are dropped when they end up the
+    /// same.
+    pub fn set_plural(&mut self, project: &Project, project_chain: &[u16], lang: &str, category: &str,
+                      value: String) {
+        if category == "other" {
+            self.set_value(project, project_chain, lang, value.to_string());
+        } else if !self.in_projects(project_chain) {
+            self.projects.push(project.id);
+        }
+
+        let inherited = self.plurals_in(&project_chain[1..], lang).cloned();
+        let project_plurals = self.plurals.entry(project.id).or_default();
+        let forms = project_plurals.entry(lang.to_string())
+            .or_insert_with(|| inherited.clone().unwrap_or_default());
+        forms.insert(category.to_string(), value);
+
+        if inherited.as_ref() == Some(forms) {
+            project_plurals.remove(lang);
+            if project_plurals.is_empty() {
+                self.plurals.remove(&project.id);
+            }
+        }
+    }
+
+    /// Plural forms used in project with parents, taken from the first project in chain which
+    /// has them.
+    pub fn plurals_in(&self, project_chain: &[u16], lang: &str) -> Option<&BTreeMap<String, String>> {
+        project_chain.iter()
+            .find_map(|id| self.plurals.get(id).and_then(|p| p.get(lang)))
+    }
+
+    pub fn is_plural(&self) -> bool {
+        self.plurals.values().any(|langs| !langs.is_empty())
+    }
+
+    /// Value stored in project, without the shared one.
+    pub fn value(&self, project_id: u16, lang: &str) -> Option<&String> {
+        self.values.get(&project_id)
+            .and_then(|v| v.get(lang))
+    }
+
+    /// Value used in project, its own or the shared one.
+    pub fn resolve(&self, project_id: u16, lang: &str) -> Option<&String> {
+        self.resolve_in(&[project_id], lang)
+    }
+
+    /// Value used in project with parents, see [`project_chain`]. The first project in chain with
+    /// a value wins, then the shared value is used.
+    pub fn resolve_in(&self, project_chain: &[u16], lang: &str) -> Option<&String> {
+        project_chain.iter()
+            .find_map(|&id| self.value(id, lang))
+            .or_else(|| self.shared.get(lang))
+    }
+
+    /// Checks if key is used by any project in chain.
+    pub fn in_projects(&self, project_chain: &[u16]) -> bool {
+        project_chain.iter().any(|id| self.projects.contains(id))
+    }
+
+    pub fn is_shared(&self, project_id: u16, lang: &str) -> bool {
+        self.value(project_id, lang).is_none() && self.shared.contains_key(lang)
+    }
+
+    /// Translation is outdated when the default language value changed after it was translated.
+    /// Translations inherited from parents are checked with the fingerprint recorded in the parent
+    /// which has the value, unless project confirmed them itself, against the project source.
+    pub fn is_outdated(&self, project: &Project, project_chain: &[u16], lang: &str) -> bool {
+        if lang == project.default_lang || self.resolve_in(project_chain, lang).is_none() {
+            return false;
+        }
+
+        let supplier = self.supplier(project_chain, lang);
+        let mut recorded = None;
+        for id in project_chain {
+            recorded = self.sources.get(id).and_then(|s| s.get(lang));
+            if recorded.is_some() || supplier == Some(*id) {
+                break;
+            }
+        }
+
+        match (recorded, self.resolve_in(project_chain, &project.default_lang)) {
+            (Some(recorded), Some(source)) => recorded.ne(&fingerprint(source)),
+            _ => false
+        }
+    }
+
+    pub fn outdated_langs(&self, project: &Project, project_chain: &[u16]) -> Vec<String> {
+        project.langs.iter()
+            .filter(|lang| self.is_outdated(project, project_chain, lang))
+            .cloned()
+            .collect()
+    }
+
+    /// Copies project membership and values to another project.
+    pub fn copy_project(&mut self, from_id: u16, to_id: u16) {
+        if !self.projects.contains(&from_id) || self.projects.contains(&to_id) {
+            return;
+        }
+
+        self.projects.push(to_id);
+
+        if let Some(values) = self.values.get(&from_id).cloned() {
+            self.values.insert(to_id, values);
+        }
+        if let Some(sources) = self.sources.get(&from_id).cloned() {
+            self.sources.insert(to_id, sources);
+        }
+        if let Some(plurals) = self.plurals.get(&from_id).cloned() {
+            self.plurals.insert(to_id, plurals);
+        }
+    }
+
+    pub fn remove_project(&mut self, project_id: u16) {
+        self.projects.retain(|&id| id != project_id);
+        self.values.remove(&project_id);
+        self.sources.remove(&project_id);
+        self.plurals.remove(&project_id);
+    }
+
+    pub fn remove_lang(&mut self, project_id: u16, lang: &str) {
+        if let Some(values) = self.values.get_mut(&project_id) {
+            values.remove(lang);
+        }
+        if let Some(sources) = self.sources.get_mut(&project_id) {
+            sources.remove(lang);
+        }
+        if let Some(plurals) = self.plurals.get_mut(&project_id) {
+            plurals.remove(lang);
+        }
+    }
+
+    /// Marks translation as reviewed against the current default language value.
+    pub fn confirm(&mut self, project: &Project, project_chain: &[u16], lang: &str) {
+        if lang != project.default_lang && self.resolve_in(project_chain, lang).is_some() {
+            self.stamp_source(project, project_chain, lang);
+        }
+    }
+
+    /// Treats all current translations in project as up to date with the default language value.
+    pub fn stamp_sources(&mut self, project: &Project, project_chain: &[u16]) {
+        self.sources.remove(&project.id);
+        self.stamp_unknown_sources(project, project_chain);
+    }
+
+    fn remove_override(&mut self, project_id: u16, lang: &str) {
+        if let Some(values) = self.values.get_mut(&project_id) {
+            values.remove(lang);
+
+            if values.is_empty() {
+                self.values.remove(&project_id);
+            }
+        }
+    }
+
+    /// Project in chain which stores the value, `None` when it is shared or missing.
+    fn supplier(&self, project_chain: &[u16], lang: &str) -> Option<u16> {
+        project_chain.iter()
+            .copied()
+            .find(|&id| self.value(id, lang).is_some())
+    }
+
+    fn stamp_source(&mut self, project: &Project, project_chain: &[u16], lang: &str) {
+        let source = match self.resolve_in(project_chain, &project.default_lang) {
+            Some(source) => fingerprint(source),
+            None => return
+        };
+
+        self.sources.entry(project.id)
+            .or_default()
+            .insert(lang.to_string(), source);
+    }
+
+    /// Stamps translations stored before fingerprints were recorded, so they can still be
+    /// detected as outdated once the default language value changes.
+    fn stamp_unknown_sources(&mut self, project: &Project, project_chain: &[u16]) {
+        let langs: Vec<String> = project.langs.iter()
+            .filter(|&lang| lang.ne(&project.default_lang))
+            .filter(|&lang| self.resolve(project.id, lang).is_some())
+            .filter(|&lang| !self.sources.get(&project.id).is_some_and(|s| s.contains_key(lang)))
+            .cloned()
+            .collect();
+
+        for lang in langs {
+            self.stamp_source(project, project_chain, &lang);
+        }
+    }
+}
+
+/// FNV-1a hash of the text, stable between runs and platforms.
+pub fn fingerprint(text: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for byte in text.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    format!("{:016x}", hash)
+}
+
+pub fn find_project<'a>(projects: &'a [Project], name: &str) -> Option<&'a Project> {
+    projects.iter().find(|&p| p.name.eq(name))
+}
+
+pub fn find_project_mut<'a>(projects: &'a mut [Project], name: &str) -> Option<&'a mut Project> {
+    projects.iter_mut().find(|p| p.name.eq(name))
+}
+
+/// Ids of the project and its parents, starting from the project itself.
+pub fn project_chain(projects: &[Project], project: &Project) -> Vec<u16> {
+    let mut chain = vec![project.id];
+    let mut parent = project.parent;
+
+    while let Some(parent_id) = parent {
+        if chain.contains(&parent_id) {
+            break;
+        }
+
+        chain.push(parent_id);
+        parent = projects.iter()
+            .find(|p| p.id == parent_id)
+            .and_then(|p| p.parent);
+    }
+
+    chain
+}
+
+/// Copy of the project with languages inherited from its parents, parent languages go first.
+pub fn inherited_project(projects: &[Project], project: &Project) -> Project {
+    let mut langs: Vec<String> = vec![];
+
+    for id in project_chain(projects, project).iter().rev() {
+        let chain_project = match projects.iter().find(|p| p.id == *id) {
+            Some(p) => p,
+            None => continue
+        };
+
+        for lang in &chain_project.langs {
+            if !langs.contains(lang) {
+                langs.push(lang.to_string());
+            }
+        }
+    }
+
+    Project { langs, ..project.clone() }
 }
 
 pub fn get_projects(data: &str) -> Result<DataRoot> {
@@ -37,22 +373,16 @@ pub fn generate_template(file_name: &str) -> std::io::Result<()> {
         id: 1,
         langs: vec!["en-US".to_string(), "pl-PL".to_string()],
         name: "TestProject".to_string(),
-        default_lang: "en-US".to_string()
+        default_lang: "en-US".to_string(),
+        parent: None
     };
 
-    let mut values_map = BTreeMap::new();
-    let mut values_lang_map = BTreeMap::new();
-    values_lang_map.entry("en-US".to_string()).or_insert("Hello World!".to_string());
-    values_lang_map.entry("pl-PL".to_string()).or_insert("Witaj świecie!".to_string());
-    values_map.entry(1).or_insert(values_lang_map);
-
-    let translation = Translation {
-        projects: vec![1],
-        values: values_map
-    };
+    let mut translation = Translation::default();
+    translation.set_value(&project, &[project.id], "en-US", "Hello World!".to_string());
+    translation.set_value(&project, &[project.id], "pl-PL", "Witaj świecie!".to_string());
 
     let mut keys_map = BTreeMap::new();
-    keys_map.entry("app.title".to_string()).or_insert(translation);
+    keys_map.insert("app.title".to_string(), translation);
 
     let data_root = DataRoot { projects: vec![project], translations: keys_map };
     save(file_name, &data_root)
@@ -68,4 +398,95 @@ pub fn save(file_name: &str, data_root: &DataRoot) -> std::io::Result<()> {
     file.flush()?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_data::{Project, Translation};
+
+    fn project() -> Project {
+        Project {
+            id: 1,
+            name: "Test".to_string(),
+            langs: vec!["en-US".to_string(), "pl-PL".to_string()],
+            default_lang: "en-US".to_string(),
+            parent: None
+        }
+    }
+
+    #[test]
+    fn changed_source_marks_translation_outdated() {
+        let project = project();
+        let mut translation = Translation::default();
+        translation.set_value(&project, &[1], "en-US", "Hello".to_string());
+        translation.set_value(&project, &[1], "pl-PL", "Witaj".to_string());
+        assert!(!translation.is_outdated(&project, &[1], "pl-PL"));
+
+        translation.set_value(&project, &[1], "en-US", "Hello!".to_string());
+        assert!(translation.is_outdated(&project, &[1], "pl-PL"));
+        assert!(!translation.is_outdated(&project, &[1], "en-US"));
+
+        translation.set_value(&project, &[1], "pl-PL", "Witaj!".to_string());
+        assert!(!translation.is_outdated(&project, &[1], "pl-PL"));
+    }
+
+    #[test]
+    fn unchanged_source_keeps_translation() {
+        let project = project();
+        let mut translation = Translation::default();
+        translation.values.entry(1).or_default().insert("en-US".to_string(), "Hello".to_string());
+        translation.values.entry(1).or_default().insert("pl-PL".to_string(), "Witaj".to_string());
+
+        translation.set_value(&project, &[1], "en-US", "Hello".to_string());
+        assert!(translation.outdated_langs(&project, &[1]).is_empty());
+
+        translation.set_value(&project, &[1], "en-US", "Hi".to_string());
+        assert_eq!(translation.outdated_langs(&project, &[1]), vec!["pl-PL".to_string()]);
+    }
+
+    #[test]
+    fn project_value_overrides_shared() {
+        let projects = vec![project()];
+        let mut translation = Translation { projects: vec![1], ..Translation::default() };
+        translation.set_shared_value(&projects, "en-US", "Hello".to_string());
+        translation.set_shared_value(&projects, "pl-PL", "Witaj".to_string());
+
+        translation.set_value(&projects[0], &[1], "pl-PL", "Cześć".to_string());
+        assert_eq!("Cześć", translation.resolve(1, "pl-PL").unwrap());
+        assert_eq!("Hello", translation.resolve(1, "en-US").unwrap());
+        assert!(translation.is_shared(1, "en-US"));
+
+        translation.set_value(&projects[0], &[1], "pl-PL", "Witaj".to_string());
+        assert!(translation.is_shared(1, "pl-PL"));
+        assert!(translation.values.is_empty());
+
+        translation.set_shared_value(&projects, "en-US", "Hello!".to_string());
+        assert!(translation.is_outdated(&projects[0], &[1], "pl-PL"));
+    }
+
+    #[test]
+    fn inherited_translation_outdated_by_source_change() {
+        let parent = project();
+        let child = Project { id: 2, parent: Some(1), ..project() };
+        let mut translation = Translation::default();
+        translation.set_value(&parent, &[1], "en-US", "Hello".to_string());
+        translation.set_value(&parent, &[1], "pl-PL", "Witaj".to_string());
+
+        translation.set_value(&child, &[2, 1], "pl-PL", "Witaj".to_string());
+        assert!(!translation.values.contains_key(&2));
+        assert!(!translation.is_outdated(&child, &[2, 1], "pl-PL"));
+
+        translation.set_value(&parent, &[1], "en-US", "Hello!".to_string());
+        assert!(translation.is_outdated(&child, &[2, 1], "pl-PL"));
+
+        translation.set_value(&parent, &[1], "pl-PL", "Witaj!".to_string());
+        assert!(!translation.is_outdated(&child, &[2, 1], "pl-PL"));
+
+        translation.set_value(&child, &[2, 1], "en-US", "Hi".to_string());
+        assert!(translation.is_outdated(&child, &[2, 1], "pl-PL"));
+        assert!(!translation.is_outdated(&parent, &[1], "pl-PL"));
+
+        translation.confirm(&child, &[2, 1], "pl-PL");
+        assert!(!translation.is_outdated(&child, &[2, 1], "pl-PL"));
+    }
+}
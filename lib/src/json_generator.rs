@@ -0,0 +1,153 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, LineWriter, Write};
+
+use serde_json::{Map, Value};
+
+use crate::placeholders::{argument_name, count_index, replace_placeholders};
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// i18next / vue-i18n JSON of a language. Dotted keys become nested objects unless `nested` is
+/// off, plural forms are written as `key_one`, `key_other`, ... and placeholders as `{{arg1}}`,
+/// with the plural count named `{{count}}`.
+pub struct TranslationsJson {
+    pub lang: String,
+    pub nested: bool,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsJson {
+    fn generate(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.document()?)?;
+
+        let file = File::create(format!("{}.json", self.lang))?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(json.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsJson {
+    /// Document with all values of the language. In nested mode a key which is also a parent of
+    /// other keys, like `a.b` and `a.b.c`, is an error, all such keys are reported.
+    fn document(&self) -> std::io::Result<Value> {
+        let mut root = Map::new();
+        let mut collisions: Vec<String> = vec![];
+
+        for (key, value) in self.translations.iter().filter(|t| !t.missing).flat_map(TranslationsJson::messages) {
+            if !self.nested {
+                root.insert(key, Value::String(value));
+            } else if !insert_nested(&mut root, &key, Value::String(value)) {
+                collisions.push(key);
+            }
+        }
+
+        if !collisions.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("keys colliding with values of their parent keys in nested json: {}",
+                                          collisions.join(", "))));
+        }
+
+        Ok(Value::Object(root))
+    }
+
+    /// Keys and values of the translation, one per plural form for plural keys.
+    fn messages(translation: &TranslationOut) -> Vec<(String, String)> {
+        if translation.plurals.is_empty() {
+            return vec![(translation.key.to_string(), format(&translation.value, None))];
+        }
+
+        let count_index = count_index(&translation.value);
+
+        translation.plurals.iter()
+            .map(|(category, value)| (format!("{}_{}", translation.key, category), format(value, count_index)))
+            .collect()
+    }
+}
+
+fn format(value: &str, count_index: Option<usize>) -> String {
+    replace_placeholders(value, |p| format!("{{{{{}}}}}", argument_name(p, count_index)))
+}
+
+/// Inserts value under dotted key path, returns `false` when the path collides with another key.
+pub(crate) fn insert_nested(root: &mut Map<String, Value>, key: &str, value: Value) -> bool {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let leaf = parts.pop().unwrap_or(key);
+    let mut node = root;
+
+    for part in parts {
+        let child = node.entry(part.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        node = match child {
+            Value::Object(map) => map,
+            _ => return false
+        };
+    }
+
+    if node.contains_key(leaf) {
+        return false;
+    }
+
+    node.insert(leaf.to_string(), value);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_generator::TranslationsJson;
+    use crate::strings_generator::TranslationOut;
+
+    fn translations() -> Vec<TranslationOut> {
+        let mut files = TranslationOut::new("app.files", "%d files");
+        files.plurals.insert("one".to_string(), "%d file".to_string());
+        files.plurals.insert("other".to_string(), "%d files".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+        let hello = TranslationOut::new("app.hello", "Hello %s, %2$s%%");
+
+        vec![files, hello, TranslationOut::new("title", "Title"), missing]
+    }
+
+    #[test]
+    fn generate_nested_json() {
+        let generator = TranslationsJson { lang: "en-US".to_string(), nested: true, translations: translations() };
+        let json = generator.document().unwrap();
+
+        assert_eq!("Hello {{arg1}}, {{arg2}}%", json["app"]["hello"]);
+        assert_eq!("{{count}} file", json["app"]["files_one"]);
+        assert_eq!("{{count}} files", json["app"]["files_other"]);
+        assert_eq!("Title", json["title"]);
+        assert!(json["app"]["missing"].is_null());
+    }
+
+    #[test]
+    fn generate_flat_json() {
+        let generator = TranslationsJson { lang: "en-US".to_string(), nested: false, translations: translations() };
+        let json = generator.document().unwrap();
+
+        assert_eq!("Hello {{arg1}}, {{arg2}}%", json["app.hello"]);
+        assert_eq!("{{count}} file", json["app.files_one"]);
+    }
+
+    #[test]
+    fn report_nested_json_collisions() {
+        let generator = TranslationsJson {
+            lang: "en-US".to_string(),
+            nested: true,
+            translations: vec![
+                TranslationOut::new("a.b", "Leaf"),
+                TranslationOut::new("a.b.c", "Child"),
+                TranslationOut::new("a.d", "Other")
+            ]
+        };
+
+        let error = generator.document().unwrap_err();
+        assert!(error.to_string().ends_with(": a.b.c"));
+
+        let generator = TranslationsJson { nested: false, ..generator };
+        assert!(generator.document().is_ok());
+    }
+}
@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::json_data::{DataRoot, Translation, find_project, inherited_project, project_chain};
+
+#[derive(Debug, Clone)]
+pub enum KeyError {
+    KeyExists(String),
+    KeyNotFound(String),
+    ProjectNotFound(String),
+    InvalidLanguage(String)
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::KeyExists(key) => write!(f, "key \"{}\" already exists", key),
+            KeyError::KeyNotFound(key) => write!(f, "key \"{}\" not found", key),
+            KeyError::ProjectNotFound(name) => write!(f, "project \"{}\" not found", name),
+            KeyError::InvalidLanguage(lang) => write!(f, "invalid language \"{}\" for project", lang)
+        }
+    }
+}
+
+impl Error for KeyError {}
+
+type Result<T> = std::result::Result<T, KeyError>;
+
+/// Adds key to projects. Existing key is only added to the projects it is not part of yet,
+/// including the ones inheriting it from a parent project.
+pub fn add_key(data_root: &mut DataRoot, key: &str, project_names: &[&str], value: Option<&str>) -> Result<()> {
+    let mut projects = vec![];
+    for name in project_names {
+        let project = find_project(&data_root.projects, name)
+            .ok_or_else(|| KeyError::ProjectNotFound(name.to_string()))?;
+        projects.push((inherited_project(&data_root.projects, project), project_chain(&data_root.projects, project)));
+    }
+
+    if let Some(translation) = data_root.translations.get(key) {
+        if projects.iter().all(|(_, chain)| translation.in_projects(chain)) {
+            return Err(KeyError::KeyExists(key.to_string()));
+        }
+    }
+
+    let translation = data_root.translations.entry(key.to_string())
+        .or_default();
+
+    for (project, chain) in projects {
+        if translation.in_projects(&chain) {
+            continue;
+        }
+
+        match value {
+            Some(value) => translation.set_value(&project, &chain, &project.default_lang, value.to_string()),
+            None => translation.projects.push(project.id)
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_key_value(data_root: &mut DataRoot, key: &str, project_name: &str, lang: &str, value: &str) -> Result<()> {
+    let project = find_project(&data_root.projects, project_name)
+        .ok_or_else(|| KeyError::ProjectNotFound(project_name.to_string()))?;
+
+    let chain = project_chain(&data_root.projects, project);
+    let project = inherited_project(&data_root.projects, project);
+    if !project.langs.iter().any(|l| l.eq(lang)) {
+        return Err(KeyError::InvalidLanguage(lang.to_string()));
+    }
+
+    let translation = data_root.translations.get_mut(key)
+        .ok_or_else(|| KeyError::KeyNotFound(key.to_string()))?;
+
+    translation.set_value(&project, &chain, lang, value.to_string());
+
+    Ok(())
+}
+
+/// Sets value shared by all projects of the key.
+pub fn set_shared_key_value(data_root: &mut DataRoot, key: &str, lang: &str, value: &str) -> Result<()> {
+    let translation = data_root.translations.get_mut(key)
+        .ok_or_else(|| KeyError::KeyNotFound(key.to_string()))?;
+
+    let projects = &data_root.projects;
+    let known_lang = projects.iter()
+        .filter(|p| translation.projects.contains(&p.id))
+        .any(|p| inherited_project(projects, p).langs.iter().any(|l| l.eq(lang)));
+
+    if !known_lang {
+        return Err(KeyError::InvalidLanguage(lang.to_string()));
+    }
+
+    translation.set_shared_value(projects, lang, value.to_string());
+
+    Ok(())
+}
+
+pub fn get_key<'a>(data_root: &'a DataRoot, key: &str) -> Result<&'a Translation> {
+    data_root.translations.get(key)
+        .ok_or_else(|| KeyError::KeyNotFound(key.to_string()))
+}
+
+/// Renames key in all projects.
+pub fn rename_key(data_root: &mut DataRoot, key: &str, new_key: &str) -> Result<()> {
+    if data_root.translations.contains_key(new_key) {
+        return Err(KeyError::KeyExists(new_key.to_string()));
+    }
+
+    let translation = data_root.translations.remove(key)
+        .ok_or_else(|| KeyError::KeyNotFound(key.to_string()))?;
+    data_root.translations.insert(new_key.to_string(), translation);
+
+    Ok(())
+}
+
+pub fn delete_key(data_root: &mut DataRoot, key: &str) -> Result<Translation> {
+    data_root.translations.remove(key)
+        .ok_or_else(|| KeyError::KeyNotFound(key.to_string()))
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use crate::android_generator::android_name;
+use crate::placeholders::{argument_name, count_index, parse_placeholders, PlaceholderType, COUNT_ARGUMENT};
+use crate::strings_generator::{unique_names, Generator, TranslationOut};
+
+const KOTLIN_KEYWORDS: [&str; 28] = [
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in", "interface", "is",
+    "null", "object", "package", "return", "super", "this", "throw", "true", "try", "typealias", "typeof",
+    "val", "var", "when", "while"
+];
+
+/// Kotlin accessors for the Android string resources, written to `L10n.kt` in `package`, which has
+/// to be the package of the app `R` class. Every key is a function of `L10n` taking `Context` and
+/// arguments typed after the default language placeholders.
+pub struct TranslationsKotlin {
+    pub package: String,
+    /// Default language strings.
+    pub sources: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsKotlin {
+    fn generate(&self) -> std::io::Result<()> {
+        let source = self.source()?;
+
+        let file = File::create("L10n.kt")?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(source.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsKotlin {
+    /// Kotlin source of the accessors. Keys which map to the same function name are an error.
+    fn source(&self) -> std::io::Result<String> {
+        let mut members = vec![];
+        let sources = self.sources.iter().filter(|t| !t.missing);
+
+        for (name, source) in unique_names(sources, "L10n.kt", kotlin_identifier)? {
+            members.push(accessor(&name, source));
+        }
+
+        Ok(format!("// Generated by stringc, do not edit.\npackage {}\n\nimport android.content.Context\n\n\
+                    object L10n {{\n{}}}\n", self.package, members.join("\n")))
+    }
+}
+
+/// Function calling `getString` or `getQuantityString` for plural keys, which take the count as
+/// the first parameter.
+fn accessor(name: &str, source: &TranslationOut) -> String {
+    let plural = !source.plurals.is_empty();
+    let count_index = if plural { count_index(&source.value) } else { None };
+
+    let arguments: BTreeMap<usize, (String, PlaceholderType)> = parse_placeholders(&source.value).iter()
+        .map(|p| (p.index, (argument_name(p, count_index), p.kind)))
+        .collect();
+
+    let mut parameters = vec!["context: Context".to_string()];
+    if plural && count_index.is_none() {
+        parameters.push(format!("{}: Int", COUNT_ARGUMENT));
+    }
+    parameters.extend(arguments.values().map(|(name, kind)| format!("{}: {}", name, kotlin_type(*kind))));
+
+    let resource = android_name(&source.key);
+    let mut call = if plural {
+        format!("context.resources.getQuantityString(R.plurals.{}, {}", resource, COUNT_ARGUMENT)
+    } else {
+        format!("context.getString(R.string.{}", resource)
+    };
+    for (name, _) in arguments.values() {
+        call.push_str(&format!(", {}", name));
+    }
+
+    format!("{}    fun {}({}): String =\n        {})\n", doc_comment(&source.value), name, parameters.join(", "), call)
+}
+
+/// KDoc with the value, comments nest in Kotlin, so both `/*` and `*/` are escaped.
+fn doc_comment(value: &str) -> String {
+    let lines: Vec<String> = value.replace("*/", "*&#47;").replace("/*", "/&#42;").lines()
+        .map(|line| line.trim_end().to_string())
+        .collect();
+
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("    /** {} */\n", line),
+        lines => {
+            let body: Vec<String> = lines.iter().map(|line| format!("     * {}", line).trim_end().to_string()).collect();
+            format!("    /**\n{}\n     */\n", body.join("\n"))
+        }
+    }
+}
+
+/// Converts key to lower camel case Kotlin identifier, e.g. `app.title` to `appTitle`. Keywords
+/// are escaped with backticks.
+fn kotlin_identifier(key: &str) -> String {
+    let mut identifier = String::new();
+
+    for word in android_name(key).split('_').filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        let first = chars.next().unwrap_or_default();
+
+        if identifier.is_empty() {
+            identifier.push(first.to_ascii_lowercase());
+        } else {
+            identifier.push(first.to_ascii_uppercase());
+        }
+        identifier.push_str(chars.as_str());
+    }
+
+    if KOTLIN_KEYWORDS.contains(&identifier.as_str()) {
+        identifier = format!("`{}`", identifier);
+    }
+
+    identifier
+}
+
+fn kotlin_type(kind: PlaceholderType) -> &'static str {
+    match kind {
+        PlaceholderType::Text => "String",
+        PlaceholderType::Int => "Int",
+        PlaceholderType::Double => "Double"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kotlin_generator::{doc_comment, kotlin_identifier, TranslationsKotlin};
+    use crate::strings_generator::TranslationOut;
+
+    #[test]
+    fn convert_keys_to_identifiers() {
+        assert_eq!("appMainTitle", kotlin_identifier("app.main_title"));
+        assert_eq!("key1st", kotlin_identifier("1st"));
+        assert_eq!("`object`", kotlin_identifier("object"));
+    }
+
+    #[test]
+    fn generate_kotlin() {
+        let mut files = TranslationOut::new("app.files", "%s has %d files");
+        files.plurals.insert("one".to_string(), "%s has %d file".to_string());
+        let mut items = TranslationOut::new("app.items", "Items");
+        items.plurals.insert("one".to_string(), "Item".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsKotlin {
+            package: "com.example.app".to_string(),
+            sources: vec![TranslationOut::new("app.title", "Title"), files, items, missing]
+        };
+
+        assert_eq!("// Generated by stringc, do not edit.\npackage com.example.app\n\n\
+                    import android.content.Context\n\n\
+                    object L10n {\n    \
+                        /** Title */\n    \
+                        fun appTitle(context: Context): String =\n        \
+                            context.getString(R.string.app_title)\n\n    \
+                        /** %s has %d files */\n    \
+                        fun appFiles(context: Context, arg1: String, count: Int): String =\n        \
+                            context.resources.getQuantityString(R.plurals.app_files, count, arg1, count)\n\n    \
+                        /** Items */\n    \
+                        fun appItems(context: Context, count: Int): String =\n        \
+                            context.resources.getQuantityString(R.plurals.app_items, count)\n\
+                    }\n", generator.source().unwrap());
+    }
+
+    #[test]
+    fn escape_doc_comment() {
+        assert_eq!("    /** Path /&#42;.txt, end *&#47; */\n", doc_comment("Path /*.txt, end */"));
+        assert_eq!("    /** a/&#42;&#47;b */\n", doc_comment("a/*/b"));
+    }
+}
@@ -4,6 +4,34 @@ pub mod strings_generator;
 pub mod excel_writer;
 pub mod excel_reader;
 pub mod excel_file;
+pub mod key_manager;
+pub mod project_manager;
+pub mod xcstrings_generator;
+pub mod xcstrings_reader;
+pub mod xliff_generator;
+pub mod xliff_reader;
+pub mod po_generator;
+pub mod po_reader;
+pub mod mo_generator;
+pub mod placeholders;
+pub mod arb_generator;
+pub mod json_generator;
+pub mod properties_generator;
+pub mod resx_generator;
+pub mod ts_generator;
+pub mod ts_reader;
+pub mod yaml_generator;
+pub mod webext_generator;
+pub mod fluent_generator;
+pub mod swift_generator;
+pub mod android_generator;
+pub mod kotlin_generator;
+pub mod typescript_generator;
+pub mod rust_generator;
+pub mod strings_reader;
+pub mod android_reader;
+pub mod encoding;
+mod xml;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::po_generator::{header_fields, plural_forms};
+use crate::strings_generator::{Generator, TranslationOut};
+
+const MAGIC: u32 = 0x9504_12de;
+const HEADER_SIZE: u32 = 28;
+/// Separates msgctxt from msgid in original strings.
+const CONTEXT_SEPARATOR: u8 = 0x04;
+
+/// Compiled gettext catalog of a project language, with the same entries as `.po` file. Unlike
+/// msgfmt, outdated translations are kept. Missing ones are left out, so gettext falls back to
+/// the source value.
+pub struct TranslationsMo {
+    pub project_name: String,
+    pub lang: String,
+    /// Default language strings.
+    pub sources: Vec<TranslationOut>,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsMo {
+    fn generate(&self) -> std::io::Result<()> {
+        let file_name = format!("Localized_{}.mo", self.lang);
+
+        let file = File::create(file_name)?;
+        let mut file = BufWriter::new(file);
+
+        file.write_all(&self.catalog())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsMo {
+    fn catalog(&self) -> Vec<u8> {
+        let entries = self.entries();
+        let count = entries.len() as u32;
+        let hash_size = hash_table_size(count);
+
+        let originals_offset = HEADER_SIZE;
+        let translations_offset = originals_offset + count * 8;
+        let hash_offset = translations_offset + count * 8;
+        let mut string_offset = hash_offset + hash_size * 4;
+
+        let mut out: Vec<u8> = vec![];
+        for value in [MAGIC, 0, count, originals_offset, translations_offset, hash_size, hash_offset].iter() {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut strings: Vec<u8> = vec![];
+        let mut translations_table: Vec<u8> = vec![];
+
+        for (original, _) in &entries {
+            out.extend_from_slice(&(original.len() as u32).to_le_bytes());
+            out.extend_from_slice(&string_offset.to_le_bytes());
+            strings.extend_from_slice(original);
+            strings.push(0);
+            string_offset += original.len() as u32 + 1;
+        }
+
+        for (_, translation) in &entries {
+            translations_table.extend_from_slice(&(translation.len() as u32).to_le_bytes());
+            translations_table.extend_from_slice(&string_offset.to_le_bytes());
+            strings.extend_from_slice(translation);
+            strings.push(0);
+            string_offset += translation.len() as u32 + 1;
+        }
+
+        out.extend_from_slice(&translations_table);
+        for slot in hash_table(&entries, hash_size) {
+            out.extend_from_slice(&slot.to_le_bytes());
+        }
+        out.extend_from_slice(&strings);
+
+        out
+    }
+
+    /// Original and translated strings, sorted by original as gettext requires. Plural strings
+    /// have their forms separated by NUL.
+    fn entries(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let translations: BTreeMap<&str, &TranslationOut> = self.translations.iter()
+            .filter(|t| !t.missing)
+            .map(|t| (t.key.as_str(), t))
+            .collect();
+
+        let mut entries = BTreeMap::new();
+        let header = format!("{}\n", header_fields(&self.project_name, Some(&self.lang)).join("\n"));
+        entries.insert(vec![], header.into_bytes());
+
+        for source in &self.sources {
+            let translation = match translations.get(source.key.as_str()) {
+                Some(translation) => translation,
+                None => continue
+            };
+
+            let mut original = source.key.as_bytes().to_vec();
+            original.push(CONTEXT_SEPARATOR);
+
+            if source.plurals.is_empty() && translation.plurals.is_empty() {
+                original.extend_from_slice(source.value.as_bytes());
+                entries.insert(original, translation.value.as_bytes().to_vec());
+                continue;
+            }
+
+            let source_form = |category: &str| source.plurals.get(category).unwrap_or(&source.value);
+            original.extend_from_slice(source_form("one").as_bytes());
+            original.push(0);
+            original.extend_from_slice(source_form("other").as_bytes());
+
+            let forms: Vec<&str> = plural_forms(&self.lang).categories.iter()
+                .map(|category| translation.plurals.get(*category).unwrap_or(&translation.value).as_str())
+                .collect();
+            entries.insert(original, forms.join("\0").into_bytes());
+        }
+
+        entries.into_iter().collect()
+    }
+}
+
+/// Hash table with open addressing used by gettext for lookups, slots hold string index + 1.
+fn hash_table(entries: &[(Vec<u8>, Vec<u8>)], size: u32) -> Vec<u32> {
+    let mut table = vec![0u32; size as usize];
+
+    for (idx, (original, _)) in entries.iter().enumerate() {
+        let hash = hash_string(original);
+        let increment = 1 + hash % (size - 2);
+        let mut slot = hash % size;
+
+        while table[slot as usize] != 0 {
+            slot = if slot >= size - increment { slot - (size - increment) } else { slot + increment };
+        }
+
+        table[slot as usize] = idx as u32 + 1;
+    }
+
+    table
+}
+
+/// Smallest prime not lower than 4/3 of strings count, like msgfmt uses.
+fn hash_table_size(count: u32) -> u32 {
+    let mut size = (count * 4 / 3).max(3);
+
+    while (2..size).take_while(|d| d * d <= size).any(|d| size % d == 0) {
+        size += 1;
+    }
+
+    size
+}
+
+/// PJW hash of the string up to the first NUL, so plural strings are hashed by msgid only.
+fn hash_string(value: &[u8]) -> u32 {
+    let mut hash: u32 = 0;
+
+    for &byte in value.iter().take_while(|&&b| b != 0) {
+        hash = (hash << 4).wrapping_add(byte as u32);
+        let high = hash & 0xf000_0000;
+        if high != 0 {
+            hash ^= high >> 24;
+            hash ^= high;
+        }
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mo_generator::{hash_string, hash_table_size, TranslationsMo};
+    use crate::strings_generator::TranslationOut;
+
+    fn translation(key: &str, value: &str, missing: bool) -> TranslationOut {
+        TranslationOut { missing, ..TranslationOut::new(key, value) }
+    }
+
+    fn read_u32(data: &[u8], offset: u32) -> u32 {
+        let offset = offset as usize;
+        u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+    }
+
+    fn read_string(data: &[u8], table_offset: u32, idx: u32) -> &[u8] {
+        let length = read_u32(data, table_offset + idx * 8);
+        let offset = read_u32(data, table_offset + idx * 8 + 4);
+        &data[offset as usize..(offset + length) as usize]
+    }
+
+    /// Looks up translation the way gettext does, through the hash table.
+    fn lookup<'a>(data: &'a [u8], original: &[u8]) -> Option<&'a [u8]> {
+        let hash_size = read_u32(data, 20);
+        let hash_offset = read_u32(data, 24);
+        let hash = hash_string(original);
+        let increment = 1 + hash % (hash_size - 2);
+        let mut slot = hash % hash_size;
+
+        loop {
+            let idx = read_u32(data, hash_offset + slot * 4);
+            if idx == 0 {
+                return None;
+            }
+            if read_string(data, read_u32(data, 12), idx - 1) == original {
+                return Some(read_string(data, read_u32(data, 16), idx - 1));
+            }
+            slot = (slot + increment) % hash_size;
+        }
+    }
+
+    #[test]
+    fn generate_mo() {
+        let mut files_source = translation("app.files", "%d files", false);
+        files_source.plurals.insert("one".to_string(), "%d file".to_string());
+        let mut files = translation("app.files", "%d plików", false);
+        files.plurals.insert("one".to_string(), "%d plik".to_string());
+        files.plurals.insert("few".to_string(), "%d pliki".to_string());
+
+        let generator = TranslationsMo {
+            project_name: "Test".to_string(),
+            lang: "pl-PL".to_string(),
+            sources: vec![
+                files_source,
+                translation("app.hello", "Hello", false),
+                translation("app.new", "New", false)
+            ],
+            translations: vec![
+                files,
+                translation("app.hello", "Witaj", false),
+                translation("app.new", "app.new", true)
+            ]
+        };
+        let data = generator.catalog();
+
+        assert_eq!(0x9504_12de, read_u32(&data, 0));
+        assert_eq!(3, read_u32(&data, 8));
+        assert_eq!(hash_table_size(3), read_u32(&data, 20));
+        assert!(String::from_utf8_lossy(lookup(&data, b"").unwrap()).contains("Language: pl_PL"));
+        assert_eq!(b"Witaj".as_ref(), lookup(&data, b"app.hello\x04Hello").unwrap());
+        assert_eq!("%d plik\0%d pliki\0%d plików".as_bytes(), lookup(&data, "app.files\x04%d file\0%d files".as_bytes()).unwrap());
+        assert!(lookup(&data, b"app.new\x04New").is_none());
+    }
+
+    #[test]
+    fn prime_hash_table_size() {
+        assert_eq!(3, hash_table_size(1));
+        assert_eq!(11, hash_table_size(8));
+        assert_eq!(137, hash_table_size(100));
+    }
+}
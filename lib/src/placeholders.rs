@@ -0,0 +1,189 @@
+//! Printf style placeholders used in values, e.g. `%s`, `%d`, `%1$s` or `%.2f`, for formats which
+//! use named or numbered arguments instead.
+
+use std::ops::Range;
+
+pub const COUNT_ARGUMENT: &str = "count";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaceholderType {
+    Text,
+    Int,
+    Double
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placeholder {
+    /// Argument number starting from 1, explicit one for positional placeholders like `%2$s`.
+    pub index: usize,
+    pub kind: PlaceholderType,
+    /// Position of the placeholder in value.
+    pub range: Range<usize>
+}
+
+/// Finds placeholders in value. `%%` is an escaped percent sign, not a placeholder.
+pub fn parse_placeholders(value: &str) -> Vec<Placeholder> {
+    let mut placeholders = vec![];
+    let bytes = value.as_bytes();
+    let mut next_index = 1;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        if bytes[pos] != b'%' {
+            pos += 1;
+            continue;
+        }
+        if bytes.get(pos + 1) == Some(&b'%') {
+            pos += 2;
+            continue;
+        }
+
+        match parse_placeholder(bytes, pos) {
+            Some((end, explicit_index, kind)) => {
+                let index = explicit_index.unwrap_or_else(|| {
+                    next_index += 1;
+                    next_index - 1
+                });
+                placeholders.push(Placeholder { index, kind, range: pos..end });
+                pos = end;
+            }
+            None => pos += 1
+        }
+    }
+
+    placeholders
+}
+
+/// Replaces placeholders with the text returned by `replacement`, escaped percent signs are
+/// unescaped.
+pub fn replace_placeholders<F>(value: &str, replacement: F) -> String
+    where F: Fn(&Placeholder) -> String {
+    replace_placeholders_escaped(value, |text| text.to_string(), replacement)
+}
+
+/// Replaces placeholders like [`replace_placeholders`], the text between them is passed through
+/// `escape`.
+pub fn replace_placeholders_escaped<E, F>(value: &str, escape: E, replacement: F) -> String
+    where E: Fn(&str) -> String, F: Fn(&Placeholder) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+
+    for placeholder in parse_placeholders(value) {
+        out.push_str(&escape(&value[last..placeholder.range.start].replace("%%", "%")));
+        out.push_str(&replacement(&placeholder));
+        last = placeholder.range.end;
+    }
+    out.push_str(&escape(&value[last..].replace("%%", "%")));
+
+    out
+}
+
+/// Argument name of the placeholder for formats with named arguments, `count` for the plural
+/// count and `arg1`, `arg2`, ... for the other ones.
+pub fn argument_name(placeholder: &Placeholder, count_index: Option<usize>) -> String {
+    if count_index == Some(placeholder.index) {
+        COUNT_ARGUMENT.to_string()
+    } else {
+        format!("arg{}", placeholder.index)
+    }
+}
+
+/// Index of the plural count argument, the first integer placeholder of value.
+pub fn count_index(value: &str) -> Option<usize> {
+    parse_placeholders(value).iter()
+        .find(|p| p.kind == PlaceholderType::Int)
+        .map(|p| p.index)
+}
+
+/// Parses `%[index$][flags][width][.precision][length]conversion` starting at `start`.
+fn parse_placeholder(bytes: &[u8], start: usize) -> Option<(usize, Option<usize>, PlaceholderType)> {
+    let mut pos = start + 1;
+
+    let digits_end = skip_digits(bytes, pos);
+    let mut explicit_index = None;
+    if digits_end > pos && bytes.get(digits_end) == Some(&b'$') {
+        // Arguments are numbered from 1, "%0$d" is text.
+        let index: usize = std::str::from_utf8(&bytes[pos..digits_end]).ok()?.parse().ok()?;
+        if index == 0 {
+            return None;
+        }
+        explicit_index = Some(index);
+        pos = digits_end + 1;
+    }
+
+    let flags_start = pos;
+    while pos < bytes.len() && b"-+ 0#".contains(&bytes[pos]) {
+        pos += 1;
+    }
+    // Space flag is only taken with width or precision, "50% off" is text.
+    let width = bytes.get(pos).is_some_and(|b| b.is_ascii_digit() || *b == b'.');
+    if bytes[flags_start..pos].contains(&b' ') && !width {
+        return None;
+    }
+    pos = skip_digits(bytes, pos);
+    if bytes.get(pos) == Some(&b'.') {
+        pos = skip_digits(bytes, pos + 1);
+    }
+    while pos < bytes.len() && b"hlqzjt".contains(&bytes[pos]) {
+        pos += 1;
+    }
+
+    let kind = match bytes.get(pos)? {
+        b's' | b'@' | b'c' | b'S' | b'C' => PlaceholderType::Text,
+        b'd' | b'i' | b'u' | b'x' | b'X' | b'o' => PlaceholderType::Int,
+        b'f' | b'F' | b'e' | b'E' | b'g' | b'G' => PlaceholderType::Double,
+        _ => return None
+    };
+
+    Some((pos + 1, explicit_index, kind))
+}
+
+fn skip_digits(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+        pos += 1;
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::placeholders::{parse_placeholders, replace_placeholders, PlaceholderType};
+
+    #[test]
+    fn parse_printf_placeholders() {
+        let placeholders = parse_placeholders("%s has %d files (%.1f%%), %2$ld, 100%!");
+
+        assert_eq!(4, placeholders.len());
+        assert_eq!((1, PlaceholderType::Text), (placeholders[0].index, placeholders[0].kind));
+        assert_eq!((2, PlaceholderType::Int), (placeholders[1].index, placeholders[1].kind));
+        assert_eq!((3, PlaceholderType::Double), (placeholders[2].index, placeholders[2].kind));
+        assert_eq!((2, PlaceholderType::Int), (placeholders[3].index, placeholders[3].kind));
+        assert_eq!(26..31, placeholders[3].range);
+    }
+
+    #[test]
+    fn parse_percent_followed_by_space_as_text() {
+        assert!(parse_placeholders("Save 50% off").is_empty());
+        assert!(parse_placeholders("100% sure").is_empty());
+        assert!(parse_placeholders("50% discount").is_empty());
+
+        let placeholders = parse_placeholders("% 5d and %- 8.2f");
+        assert_eq!(vec![PlaceholderType::Int, PlaceholderType::Double],
+                   placeholders.iter().map(|p| p.kind).collect::<Vec<PlaceholderType>>());
+    }
+
+    #[test]
+    fn parse_zero_index_as_text() {
+        let placeholders = parse_placeholders("%0$d items, %1$d left");
+
+        assert_eq!(1, placeholders.len());
+        assert_eq!(1, placeholders[0].index);
+    }
+
+    #[test]
+    fn replace_printf_placeholders() {
+        let replaced = replace_placeholders("Hello %s, %2$d%% of %1$@", |p| format!("{{{}}}", p.index));
+
+        assert_eq!("Hello {1}, {2}% of {1}", replaced);
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// Gettext plural rule of a language, `categories` are plural categories of msgstr indexes.
+pub(crate) struct PluralForms {
+    pub nplurals: usize,
+    pub expression: &'static str,
+    pub categories: &'static [&'static str]
+}
+
+/// Gettext catalog of a project, `.po` file for a language or `.pot` template when there is no
+/// language. Keys are written as msgctxt, default language values as msgid.
+pub struct TranslationsPo {
+    pub project_name: String,
+    pub lang: Option<String>,
+    /// Default language strings.
+    pub sources: Vec<TranslationOut>,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsPo {
+    fn generate(&self) -> std::io::Result<()> {
+        let file_name = match &self.lang {
+            Some(lang) => format!("Localized_{}.po", lang),
+            None => "Localized.pot".to_string()
+        };
+
+        let file = File::create(file_name)?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(self.catalog().as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsPo {
+    fn catalog(&self) -> String {
+        let mut out = self.header();
+        let translations: BTreeMap<&str, &TranslationOut> = self.translations.iter()
+            .map(|t| (t.key.as_str(), t))
+            .collect();
+
+        for source in &self.sources {
+            out.push('\n');
+            out.push_str(&self.entry(source, translations.get(source.key.as_str()).copied()));
+        }
+
+        out
+    }
+
+    fn header(&self) -> String {
+        let mut out = String::from("msgid \"\"\nmsgstr \"\"\n");
+
+        for field in header_fields(&self.project_name, self.lang.as_deref()) {
+            out.push_str(&format!("\"{}\\n\"\n", escape(&field)));
+        }
+
+        out
+    }
+
+    fn entry(&self, source: &TranslationOut, translation: Option<&TranslationOut>) -> String {
+        let translation = translation.filter(|t| !t.missing && self.lang.is_some());
+        let mut out = String::new();
+
+        if let Some(comment) = &source.comment {
+            for line in comment.lines() {
+                out.push_str(&format!("#. {}\n", line));
+            }
+        }
+        if translation.is_some_and(|t| t.outdated) {
+            out.push_str("#, fuzzy\n");
+        }
+
+        out.push_str(&format!("msgctxt \"{}\"\n", escape(&source.key)));
+
+        let is_plural = !source.plurals.is_empty() || translation.is_some_and(|t| !t.plurals.is_empty());
+        if !is_plural {
+            out.push_str(&format!("msgid \"{}\"\n", escape(&source.value)));
+            out.push_str(&format!("msgstr \"{}\"\n", escape(translation.map_or("", |t| t.value.as_str()))));
+            return out;
+        }
+
+        let source_form = |category: &str| source.plurals.get(category).unwrap_or(&source.value);
+        out.push_str(&format!("msgid \"{}\"\n", escape(source_form("one"))));
+        out.push_str(&format!("msgid_plural \"{}\"\n", escape(source_form("other"))));
+
+        let categories = match &self.lang {
+            Some(lang) => plural_forms(lang).categories,
+            None => &["one", "other"]
+        };
+
+        for (idx, category) in categories.iter().enumerate() {
+            let value = translation
+                .map(|t| t.plurals.get(*category).unwrap_or(&t.value).as_str())
+                .unwrap_or("");
+            out.push_str(&format!("msgstr[{}] \"{}\"\n", idx, escape(value)));
+        }
+
+        out
+    }
+}
+
+/// Plural rule of the language, languages not listed use the English one.
+pub(crate) fn plural_forms(lang: &str) -> PluralForms {
+    let language = lang.split(['-', '_'].as_ref()).next().unwrap_or(lang);
+
+    match (language, lang) {
+        (_, "pt-BR") | (_, "pt_BR") | ("fr", _) => PluralForms {
+            nplurals: 2,
+            expression: "(n > 1)",
+            categories: &["one", "other"]
+        },
+        ("ja", _) | ("ko", _) | ("zh", _) | ("vi", _) | ("th", _) | ("id", _) | ("ms", _) => PluralForms {
+            nplurals: 1,
+            expression: "0",
+            categories: &["other"]
+        },
+        ("pl", _) => PluralForms {
+            nplurals: 3,
+            expression: "(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+            categories: &["one", "few", "many"]
+        },
+        ("ru", _) | ("uk", _) | ("be", _) | ("hr", _) | ("sr", _) | ("bs", _) => PluralForms {
+            nplurals: 3,
+            expression: "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+            categories: &["one", "few", "many"]
+        },
+        ("cs", _) | ("sk", _) => PluralForms {
+            nplurals: 3,
+            expression: "(n==1) ? 0 : (n>=2 && n<=4) ? 1 : 2",
+            categories: &["one", "few", "other"]
+        },
+        ("ar", _) => PluralForms {
+            nplurals: 6,
+            expression: "(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5)",
+            categories: &["zero", "one", "two", "few", "many", "other"]
+        },
+        _ => PluralForms {
+            nplurals: 2,
+            expression: "(n != 1)",
+            categories: &["one", "other"]
+        }
+    }
+}
+
+/// Catalog header fields, template has plural rule placeholders instead of the language one.
+pub(crate) fn header_fields(project_name: &str, lang: Option<&str>) -> Vec<String> {
+    let (lang, plural_forms) = match lang {
+        Some(lang) => {
+            let forms = plural_forms(lang);
+            (po_lang(lang), format!("nplurals={}; plural={};", forms.nplurals, forms.expression))
+        }
+        None => (String::new(), "nplurals=INTEGER; plural=EXPRESSION;".to_string())
+    };
+
+    vec![
+        format!("Project-Id-Version: {}", project_name),
+        format!("Language: {}", lang),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/plain; charset=UTF-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+        format!("Plural-Forms: {}", plural_forms)
+    ]
+}
+
+/// Gettext uses underscore in locale names, e.g. `pl_PL`.
+pub(crate) fn po_lang(lang: &str) -> String {
+    lang.replace('-', "_")
+}
+
+pub(crate) fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::po_generator::TranslationsPo;
+    use crate::strings_generator::TranslationOut;
+
+    fn sources() -> Vec<TranslationOut> {
+        let mut files = TranslationOut::new("app.files", "%d files");
+        files.comment = Some("Files count".to_string());
+        files.plurals.insert("one".to_string(), "%d file".to_string());
+        files.plurals.insert("other".to_string(), "%d files".to_string());
+
+        vec![files, TranslationOut::new("app.hello", "Say \"hello\"")]
+    }
+
+    #[test]
+    fn generate_po() {
+        let mut files = TranslationOut::new("app.files", "%d plików");
+        files.plurals.insert("one".to_string(), "%d plik".to_string());
+        files.plurals.insert("few".to_string(), "%d pliki".to_string());
+        let mut hello = TranslationOut::new("app.hello", "Powiedz \"cześć\"");
+        hello.outdated = true;
+
+        let generator = TranslationsPo {
+            project_name: "Test".to_string(),
+            lang: Some("pl-PL".to_string()),
+            sources: sources(),
+            translations: vec![files, hello]
+        };
+        let catalog = generator.catalog();
+
+        assert!(catalog.contains("\"Language: pl_PL\\n\""));
+        assert!(catalog.contains("Plural-Forms: nplurals=3; plural=(n==1 ? 0 :"));
+        assert!(catalog.contains("#. Files count\nmsgctxt \"app.files\"\nmsgid \"%d file\"\nmsgid_plural \"%d files\"\n\
+                                  msgstr[0] \"%d plik\"\nmsgstr[1] \"%d pliki\"\nmsgstr[2] \"%d plików\"\n"));
+        assert!(catalog.contains("#, fuzzy\nmsgctxt \"app.hello\"\nmsgid \"Say \\\"hello\\\"\"\nmsgstr \"Powiedz \\\"cześć\\\"\"\n"));
+    }
+
+    #[test]
+    fn generate_pot() {
+        let generator = TranslationsPo {
+            project_name: "Test".to_string(),
+            lang: None,
+            sources: sources(),
+            translations: vec![]
+        };
+        let catalog = generator.catalog();
+
+        assert!(catalog.contains("Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;"));
+        assert!(catalog.contains("msgid_plural \"%d files\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"\n"));
+        assert!(catalog.contains("msgid \"Say \\\"hello\\\"\"\nmsgstr \"\"\n"));
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::excel_reader::{ImportResult, Importer};
+use crate::json_data::{DataRootTranslations, Project};
+use crate::po_generator::{plural_forms, po_lang};
+
+#[derive(Debug, Clone)]
+pub enum PoError {
+    Syntax(usize),
+    MissingLanguage,
+    InvalidLanguage(String)
+}
+
+impl fmt::Display for PoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoError::Syntax(line) => write!(f, "invalid po file at line {}", line),
+            PoError::MissingLanguage => write!(f, "no Language header in po file"),
+            PoError::InvalidLanguage(lang) => write!(f, "language \"{}\" not in project", lang)
+        }
+    }
+}
+
+impl Error for PoError {}
+
+type Result<T> = std::result::Result<T, PoError>;
+
+#[derive(Default)]
+struct Entry {
+    fuzzy: bool,
+    msgctxt: Option<String>,
+    msgid: Option<String>,
+    msgid_plural: Option<String>,
+    msgstr: BTreeMap<usize, String>
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Ctxt,
+    Id,
+    IdPlural,
+    Str(usize)
+}
+
+impl Entry {
+    fn is_empty(&self) -> bool {
+        self.msgctxt.is_none() && self.msgid.is_none()
+    }
+
+    fn is_header(&self) -> bool {
+        self.msgctxt.is_none() && self.msgid.as_deref() == Some("")
+    }
+
+    fn field(&mut self, field: Field) -> &mut String {
+        match field {
+            Field::Ctxt => self.msgctxt.get_or_insert_with(String::new),
+            Field::Id => self.msgid.get_or_insert_with(String::new),
+            Field::IdPlural => self.msgid_plural.get_or_insert_with(String::new),
+            Field::Str(idx) => self.msgstr.entry(idx).or_default()
+        }
+    }
+}
+
+/// Imports translated gettext catalog into project, language is taken from the catalog header.
+/// Entries are matched to keys by msgctxt, or by msgid when there is no context. Fuzzy entries
+/// are skipped, the other ones are marked as up to date with the source value. The last plural
+/// form is imported as the `other` form too.
+pub fn import_po(data: &str, data_root: &mut DataRootTranslations, project: &Project, project_chain: &[u16],
+                 ignore_unknown: bool) -> Result<ImportResult> {
+    let entries = parse(data)?;
+
+    let header = entries.iter()
+        .find(|e| e.is_header())
+        .and_then(|e| e.msgstr.get(&0))
+        .ok_or(PoError::MissingLanguage)?;
+
+    let lang = header_lang(header).ok_or(PoError::MissingLanguage)?;
+    let lang = project.langs.iter()
+        .find(|l| po_lang(l).eq(&po_lang(&lang)))
+        .ok_or(PoError::InvalidLanguage(lang))?;
+
+    let categories = plural_forms(lang).categories;
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
+
+    for entry in entries.into_iter().filter(|e| !e.is_header() && !e.fuzzy) {
+        let key = match entry.msgctxt.or(entry.msgid) {
+            Some(key) if !key.is_empty() => key,
+            _ => continue
+        };
+
+        let mut imported = false;
+        for (idx, value) in entry.msgstr.into_iter().filter(|(_, v)| !v.is_empty()) {
+            if entry.msgid_plural.is_none() {
+                importer.value(&key, lang, value);
+            } else if let Some(category) = categories.get(idx) {
+                // Last gettext form is also the other form, which is the key value.
+                if idx == categories.len() - 1 && *category != "other" {
+                    importer.plural(&key, lang, "other", value.to_string());
+                }
+                importer.plural(&key, lang, category, value);
+            }
+            imported = true;
+        }
+
+        if imported {
+            importer.confirm(&key, lang);
+        }
+    }
+
+    Ok(importer.result)
+}
+
+fn parse(data: &str) -> Result<Vec<Entry>> {
+    let mut entries = vec![];
+    let mut entry = Entry::default();
+    let mut field: Option<Field> = None;
+
+    for (idx, line) in data.lines().enumerate() {
+        let line = line.trim();
+        let line_number = idx + 1;
+
+        if line.is_empty() || line.starts_with('#') {
+            if !entry.msgstr.is_empty() {
+                entries.push(std::mem::take(&mut entry));
+            }
+            field = None;
+
+            if line.starts_with("#,") && line.contains("fuzzy") {
+                entry.fuzzy = true;
+            }
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let current = field.ok_or(PoError::Syntax(line_number))?;
+            entry.field(current).push_str(&unquote(line, line_number)?);
+            continue;
+        }
+
+        let (keyword, value) = line.split_once(' ').ok_or(PoError::Syntax(line_number))?;
+        let next = match keyword {
+            "msgctxt" => Field::Ctxt,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::IdPlural,
+            "msgstr" => Field::Str(0),
+            _ => keyword.strip_prefix("msgstr[")
+                .and_then(|k| k.strip_suffix(']'))
+                .and_then(|k| k.parse().ok())
+                .map(Field::Str)
+                .ok_or(PoError::Syntax(line_number))?
+        };
+
+        let starts_entry = matches!(next, Field::Ctxt) || (matches!(next, Field::Id) && entry.msgid.is_some());
+        if starts_entry && !entry.msgstr.is_empty() {
+            entries.push(std::mem::take(&mut entry));
+        }
+
+        entry.field(next).push_str(&unquote(value.trim(), line_number)?);
+        field = Some(next);
+    }
+
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+fn unquote(value: &str, line_number: usize) -> Result<String> {
+    let value = value.strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or(PoError::Syntax(line_number))?;
+
+    let mut out = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(c) => out.push(c),
+            None => return Err(PoError::Syntax(line_number))
+        }
+    }
+
+    Ok(out)
+}
+
+fn header_lang(header: &str) -> Option<String> {
+    header.lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("Language"))
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|lang| !lang.is_empty())
+}
@@ -0,0 +1,234 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::json_data::{DataRoot, Project, find_project, find_project_mut, inherited_project, project_chain};
+
+#[derive(Debug, Clone)]
+pub enum ProjectError {
+    ProjectExists(String),
+    ProjectNotFound(String),
+    LangExists(String),
+    LangNotFound(String),
+    InheritedLang(String),
+    DefaultLang(String),
+    HasChildren(String),
+    NoProjectIdLeft
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::ProjectExists(name) => write!(f, "project \"{}\" already exists", name),
+            ProjectError::ProjectNotFound(name) => write!(f, "project \"{}\" not found", name),
+            ProjectError::LangExists(lang) => write!(f, "language \"{}\" already in project", lang),
+            ProjectError::LangNotFound(lang) => write!(f, "language \"{}\" not in project", lang),
+            ProjectError::InheritedLang(lang) => write!(f, "language \"{}\" is inherited from parent project", lang),
+            ProjectError::DefaultLang(lang) => write!(f, "cannot remove default language \"{}\"", lang),
+            ProjectError::HasChildren(name) => write!(f, "project \"{}\" is a parent of other projects", name),
+            ProjectError::NoProjectIdLeft => write!(f, "no project id left")
+        }
+    }
+}
+
+impl Error for ProjectError {}
+
+type Result<T> = std::result::Result<T, ProjectError>;
+
+pub fn add_project(data_root: &mut DataRoot, name: &str, default_lang: &str, langs: &[&str]) -> Result<()> {
+    check_project_name(data_root, name)?;
+
+    let mut project_langs = vec![default_lang.to_string()];
+    for lang in langs {
+        if !project_langs.iter().any(|l| l.eq(lang)) {
+            project_langs.push(lang.to_string());
+        }
+    }
+
+    let project = Project {
+        id: next_project_id(data_root)?,
+        name: name.to_string(),
+        langs: project_langs,
+        default_lang: default_lang.to_string(),
+        parent: None
+    };
+    data_root.projects.push(project);
+
+    Ok(())
+}
+
+/// Adds project which inherits keys, languages and values from the parent project, so only the
+/// differences have to be stored in it.
+pub fn derive_project(data_root: &mut DataRoot, name: &str, parent_name: &str) -> Result<()> {
+    check_project_name(data_root, name)?;
+
+    let parent = find_project(&data_root.projects, parent_name)
+        .ok_or_else(|| ProjectError::ProjectNotFound(parent_name.to_string()))?;
+
+    let project = Project {
+        id: next_project_id(data_root)?,
+        name: name.to_string(),
+        langs: vec![],
+        default_lang: parent.default_lang.to_string(),
+        parent: Some(parent.id)
+    };
+    data_root.projects.push(project);
+
+    Ok(())
+}
+
+pub fn rename_project(data_root: &mut DataRoot, name: &str, new_name: &str) -> Result<()> {
+    check_project_name(data_root, new_name)?;
+
+    let project = get_project_mut(data_root, name)?;
+    project.name = new_name.to_string();
+
+    Ok(())
+}
+
+/// Creates new project with the same languages, keys and values.
+pub fn clone_project(data_root: &mut DataRoot, name: &str, new_name: &str) -> Result<()> {
+    check_project_name(data_root, new_name)?;
+
+    let project = find_project(&data_root.projects, name)
+        .ok_or_else(|| ProjectError::ProjectNotFound(name.to_string()))?;
+
+    let clone = Project {
+        id: next_project_id(data_root)?,
+        name: new_name.to_string(),
+        langs: project.langs.to_vec(),
+        default_lang: project.default_lang.to_string(),
+        parent: project.parent
+    };
+
+    for translation in data_root.translations.values_mut() {
+        translation.copy_project(project.id, clone.id);
+    }
+
+    data_root.projects.push(clone);
+
+    Ok(())
+}
+
+/// Removes project and its values. Keys which are not used by any other project are removed too,
+/// their names are returned.
+pub fn remove_project(data_root: &mut DataRoot, name: &str) -> Result<Vec<String>> {
+    let project_id = get_project_mut(data_root, name)?.id;
+
+    if data_root.projects.iter().any(|p| p.parent == Some(project_id)) {
+        return Err(ProjectError::HasChildren(name.to_string()));
+    }
+
+    data_root.projects.retain(|p| p.id != project_id);
+
+    let mut removed_keys = vec![];
+    for (key, translation) in data_root.translations.iter_mut() {
+        translation.remove_project(project_id);
+
+        if translation.projects.is_empty() {
+            removed_keys.push(key.to_string());
+        }
+    }
+
+    for key in &removed_keys {
+        data_root.translations.remove(key);
+    }
+
+    Ok(removed_keys)
+}
+
+pub fn add_lang(data_root: &mut DataRoot, name: &str, lang: &str) -> Result<()> {
+    if project_langs(data_root, name)?.iter().any(|l| l.eq(lang)) {
+        return Err(ProjectError::LangExists(lang.to_string()));
+    }
+
+    let project = get_project_mut(data_root, name)?;
+    project.langs.push(lang.to_string());
+
+    Ok(())
+}
+
+/// Removes language from project with all its values. Derived projects lose the language too,
+/// unless they list it themselves, so their values are removed as well. Inherited language can
+/// only be removed from the parent project which lists it.
+pub fn remove_lang(data_root: &mut DataRoot, name: &str, lang: &str) -> Result<()> {
+    if !project_langs(data_root, name)?.iter().any(|l| l.eq(lang)) {
+        return Err(ProjectError::LangNotFound(lang.to_string()));
+    }
+
+    let project = get_project_mut(data_root, name)?;
+
+    if project.default_lang.eq(lang) {
+        return Err(ProjectError::DefaultLang(lang.to_string()));
+    }
+    if !project.langs.iter().any(|l| l.eq(lang)) {
+        return Err(ProjectError::InheritedLang(lang.to_string()));
+    }
+
+    project.langs.retain(|l| l.ne(lang));
+    let project_id = project.id;
+
+    let projects = &data_root.projects;
+    let removed_from: Vec<u16> = projects.iter()
+        .filter(|p| project_chain(projects, p).contains(&project_id))
+        .filter(|p| !inherited_project(projects, p).langs.iter().any(|l| l.eq(lang)))
+        .map(|p| p.id)
+        .collect();
+
+    for translation in data_root.translations.values_mut() {
+        for id in &removed_from {
+            translation.remove_lang(*id, lang);
+        }
+    }
+
+    Ok(())
+}
+
+/// Changes project source language. Current translations are treated as up to date with the new
+/// source values.
+pub fn set_default_lang(data_root: &mut DataRoot, name: &str, lang: &str) -> Result<()> {
+    if !project_langs(data_root, name)?.iter().any(|l| l.eq(lang)) {
+        return Err(ProjectError::LangNotFound(lang.to_string()));
+    }
+
+    get_project_mut(data_root, name)?.default_lang = lang.to_string();
+
+    let project = find_project(&data_root.projects, name)
+        .ok_or_else(|| ProjectError::ProjectNotFound(name.to_string()))?;
+    let chain = project_chain(&data_root.projects, project);
+    let project = inherited_project(&data_root.projects, project);
+
+    for translation in data_root.translations.values_mut() {
+        translation.stamp_sources(&project, &chain);
+    }
+
+    Ok(())
+}
+
+fn get_project_mut<'a>(data_root: &'a mut DataRoot, name: &str) -> Result<&'a mut Project> {
+    find_project_mut(&mut data_root.projects, name)
+        .ok_or_else(|| ProjectError::ProjectNotFound(name.to_string()))
+}
+
+/// Project languages, including the ones inherited from parents.
+fn project_langs(data_root: &DataRoot, name: &str) -> Result<Vec<String>> {
+    let project = find_project(&data_root.projects, name)
+        .ok_or_else(|| ProjectError::ProjectNotFound(name.to_string()))?;
+
+    Ok(inherited_project(&data_root.projects, project).langs)
+}
+
+fn check_project_name(data_root: &DataRoot, name: &str) -> Result<()> {
+    match find_project(&data_root.projects, name) {
+        Some(_) => Err(ProjectError::ProjectExists(name.to_string())),
+        None => Ok(())
+    }
+}
+
+fn next_project_id(data_root: &DataRoot) -> Result<u16> {
+    data_root.projects.iter()
+        .map(|p| p.id)
+        .max()
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or(ProjectError::NoProjectIdLeft)
+}
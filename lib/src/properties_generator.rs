@@ -0,0 +1,155 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::placeholders::replace_placeholders;
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// Java properties file of a language, values are MessageFormat patterns with `{0}`, `{1}`, ...
+/// placeholders. Without `utf8` the file is ISO-8859-1 encoded, characters outside of it are
+/// written as `\uXXXX`. Plural keys are written with their `other` form only.
+pub struct TranslationsProperties {
+    pub lang: String,
+    pub utf8: bool,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsProperties {
+    fn generate(&self) -> std::io::Result<()> {
+        let file_name = format!("messages_{}.properties", self.lang.replace('-', "_"));
+
+        let file = File::create(file_name)?;
+        let mut file = BufWriter::new(file);
+
+        file.write_all(&self.encode(&self.properties()))?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsProperties {
+    fn properties(&self) -> String {
+        let mut out = String::new();
+
+        for translation in self.translations.iter().filter(|t| !t.missing) {
+            if let Some(comment) = &translation.comment {
+                for line in comment.lines() {
+                    out.push_str(&format!("# {}\n", self.escape(line, false)));
+                }
+            }
+
+            let key = self.escape(&translation.key, true);
+            let value = self.escape(&message_format(&translation.value), false);
+            out.push_str(&format!("{}={}\n", key, value));
+        }
+
+        out
+    }
+
+    /// Escapes special characters, spaces and comment characters in values only at the start.
+    fn escape(&self, text: &str, is_key: bool) -> String {
+        let mut out = String::new();
+
+        for (idx, c) in text.chars().enumerate() {
+            let leading = idx == 0;
+
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                '\u{c}' => out.push_str("\\f"),
+                ':' | '=' => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                ' ' | '#' | '!' if is_key || leading => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                c if !self.utf8 && c as u32 > 0xff => {
+                    let mut units = [0u16; 2];
+                    for unit in c.encode_utf16(&mut units) {
+                        out.push_str(&format!("\\u{:04X}", unit));
+                    }
+                }
+                c => out.push(c)
+            }
+        }
+
+        out
+    }
+
+    fn encode(&self, properties: &str) -> Vec<u8> {
+        if self.utf8 {
+            properties.as_bytes().to_vec()
+        } else {
+            // Characters above 0xff are escaped already.
+            properties.chars().map(|c| c as u8).collect()
+        }
+    }
+}
+
+/// Converts value to MessageFormat pattern, quotes are doubled and braces quoted, so they are
+/// not treated as format elements.
+fn message_format(value: &str) -> String {
+    let quoted = value
+        .replace('\'', "''")
+        .replace('{', "'{'")
+        .replace('}', "'}'");
+
+    replace_placeholders(&quoted, |p| format!("{{{}}}", p.index - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::properties_generator::TranslationsProperties;
+    use crate::strings_generator::TranslationOut;
+
+    fn generator(utf8: bool) -> TranslationsProperties {
+        let mut hello = TranslationOut::new("app.hello", "Witaj %s, it's {you}: %2$d%%");
+        hello.comment = Some("Greeting".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        TranslationsProperties {
+            lang: "pl-PL".to_string(),
+            utf8,
+            translations: vec![hello, TranslationOut::new("app key=1", " żółw ← \\ #1\nend"), missing]
+        }
+    }
+
+    #[test]
+    fn generate_latin1_properties() {
+        let generator = generator(false);
+        let properties = generator.properties();
+
+        assert_eq!("# Greeting\n\
+                    app.hello=Witaj {0}, it''s '{'you'}'\\: {1}%\n\
+                    app\\ key\\=1=\\ \\u017C\u{f3}\\u0142w \\u2190 \\\\ #1\\nend\n", properties);
+
+        let encoded = generator.encode(&properties);
+        assert_eq!(properties.chars().count(), encoded.len());
+        assert!(encoded.contains(&0xf3));
+    }
+
+    #[test]
+    fn generate_utf8_properties() {
+        let generator = generator(true);
+        let properties = generator.properties();
+
+        assert!(properties.contains("app\\ key\\=1=\\ żółw ← \\\\ #1\\nend\n"));
+        assert_eq!(properties.as_bytes(), generator.encode(&properties).as_slice());
+    }
+
+    #[test]
+    fn generate_properties_with_zero_index() {
+        let generator = TranslationsProperties {
+            lang: "en-US".to_string(),
+            utf8: true,
+            translations: vec![TranslationOut::new("app.items", "%0$d items, %1$d left")]
+        };
+
+        assert_eq!("app.items=%0$d items, {0} left\n", generator.properties());
+    }
+}
@@ -0,0 +1,94 @@
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use quick_xml::escape::escape;
+
+use crate::placeholders::replace_placeholders;
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// .NET resources of a language, the neutral `Resources.resx` when `lang` is `None`. Values use
+/// composite format placeholders `{0}`, `{1}`, ... Plural keys are written with their `other`
+/// form only.
+pub struct TranslationsResx {
+    pub lang: Option<String>,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsResx {
+    fn generate(&self) -> std::io::Result<()> {
+        let file_name = match &self.lang {
+            Some(lang) => format!("Resources.{}.resx", lang),
+            None => "Resources.resx".to_string()
+        };
+
+        let file = File::create(file_name)?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(self.document().as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsResx {
+    fn document(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n");
+        out.push_str("  <resheader name=\"resmimetype\">\n    <value>text/microsoft-resx</value>\n  </resheader>\n");
+        out.push_str("  <resheader name=\"version\">\n    <value>2.0</value>\n  </resheader>\n");
+        out.push_str("  <resheader name=\"reader\">\n    <value>System.Resources.ResXResourceReader, System.Windows.Forms, \
+                      Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>\n  </resheader>\n");
+        out.push_str("  <resheader name=\"writer\">\n    <value>System.Resources.ResXResourceWriter, System.Windows.Forms, \
+                      Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>\n  </resheader>\n");
+
+        for translation in self.translations.iter().filter(|t| !t.missing) {
+            out.push_str(&format!("  <data name=\"{}\" xml:space=\"preserve\">\n", escape(&translation.key)));
+            out.push_str(&format!("    <value>{}</value>\n", escape(&composite_format(&translation.value))));
+            if let Some(comment) = &translation.comment {
+                out.push_str(&format!("    <comment>{}</comment>\n", escape(comment)));
+            }
+            out.push_str("  </data>\n");
+        }
+
+        out.push_str("</root>\n");
+        out
+    }
+}
+
+/// Converts value to .NET composite format, literal braces are doubled.
+fn composite_format(value: &str) -> String {
+    let escaped = value
+        .replace('{', "{{")
+        .replace('}', "}}");
+
+    replace_placeholders(&escaped, |p| format!("{{{}}}", p.index - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resx_generator::TranslationsResx;
+    use crate::strings_generator::TranslationOut;
+
+    #[test]
+    fn generate_resx() {
+        let mut hello = TranslationOut::new("app.hello", "Witaj <%s> {%2$d}");
+        hello.comment = Some("Greeting & welcome".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsResx { lang: Some("pl-PL".to_string()), translations: vec![hello, missing] };
+        let document = generator.document();
+
+        assert!(document.contains("<data name=\"app.hello\" xml:space=\"preserve\">\n    \
+                                   <value>Witaj &lt;{0}&gt; {{{1}}}</value>\n    \
+                                   <comment>Greeting &amp; welcome</comment>\n  </data>\n"));
+        assert!(!document.contains("app.missing"));
+    }
+    #[test]
+    fn generate_resx_with_zero_index() {
+        let items = TranslationOut::new("app.items", "%0$d items");
+        let generator = TranslationsResx { lang: None, translations: vec![items] };
+
+        assert!(generator.document().contains("<value>%0$d items</value>"));
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, LineWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::json_data::{find_project, get_projects};
+use crate::placeholders::{argument_name, count_index, parse_placeholders, PlaceholderType, COUNT_ARGUMENT};
+use crate::po_generator::plural_forms;
+use crate::strings_generator::{project_strings, unique_names, Generator, TranslationOut};
+
+const RUST_KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match",
+    "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
+    "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    "gen"
+];
+
+/// Functions of the generated module, which keys cannot be named after.
+const GENERATED_NAMES: [&str; 1] = ["plural_index"];
+
+/// Helpers of the generated module, pieces of values with placeholders are formatted at runtime.
+const INTERNAL_MODULE: &str = "mod internal {
+    use std::fmt::{Display, Write};
+
+    #[allow(dead_code)]
+    pub enum Piece {
+        Text(&'static str),
+        Arg(usize),
+        Precision(usize, usize)
+    }
+
+    pub fn format(pieces: &[Piece], args: &[&dyn Display]) -> String {
+        let mut out = String::new();
+
+        for piece in pieces {
+            let _ = match piece {
+                Piece::Text(text) => out.write_str(text),
+                Piece::Arg(index) => write!(out, \"{}\", args[*index]),
+                Piece::Precision(index, precision) => write!(out, \"{:.*}\", precision, args[*index])
+            };
+        }
+
+        out
+    }
+}
+";
+
+/// Rust module with project strings embedded in static tables, written to `strings.rs`. Languages
+/// are variants of the `Lang` enum and every key is a function taking `Lang` and arguments typed
+/// after the default language placeholders. Placeholders keep their precision, other printf flags
+/// are ignored. Missing translations fall back to the default language value.
+pub struct TranslationsRust {
+    pub default_lang: String,
+    pub strings: Vec<(String, Vec<TranslationOut>)>
+}
+
+impl Generator for TranslationsRust {
+    fn generate(&self) -> std::io::Result<()> {
+        write_module(&self.source()?, Path::new("strings.rs"))
+    }
+}
+
+/// Generates Rust module of the project strings into `OUT_DIR`, meant to be called from build
+/// scripts. The module is included with `include!(concat!(env!("OUT_DIR"), "/strings.rs"))`.
+pub fn generate_rust_module<P: AsRef<Path>>(data_file: P, project_name: &str) -> std::io::Result<()> {
+    let out_dir = env::var_os("OUT_DIR")
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "OUT_DIR is not set, call it from build script"))?;
+    println!("cargo:rerun-if-changed={}", data_file.as_ref().display());
+
+    let data_root = get_projects(&fs::read_to_string(data_file)?)?;
+    let project = find_project(&data_root.projects, project_name)
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("project \"{}\" not found", project_name)))?;
+
+    let generator = TranslationsRust {
+        default_lang: project.default_lang.to_string(),
+        strings: project_strings(&data_root, project)
+    };
+    write_module(&generator.source()?, &PathBuf::from(out_dir).join("strings.rs"))
+}
+
+fn write_module(source: &str, path: &Path) -> std::io::Result<()> {
+    let file = File::create(path)?;
+    let mut file = LineWriter::new(file);
+
+    file.write_all(source.as_ref())?;
+    file.flush()?;
+
+    Ok(())
+}
+
+impl TranslationsRust {
+    /// Rust source of the module. Keys or languages which map to the same identifier are an error.
+    /// Formatting and plural helpers are written only when some key uses them, so the module
+    /// does not warn about unused code.
+    fn source(&self) -> std::io::Result<String> {
+        let langs: Vec<&str> = self.strings.iter().map(|(lang, _)| lang.as_str()).collect();
+        if langs.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "no languages in project"));
+        }
+        let default_index = langs.iter().position(|lang| lang.eq(&self.default_lang)).unwrap_or_default();
+        let sources = self.strings.get(default_index).map(|(_, t)| t.as_slice()).unwrap_or_default();
+        let plural = sources.iter().any(|t| !t.plurals.is_empty());
+        let formatted = plural || sources.iter().any(|t| !parse_placeholders(&t.value).is_empty());
+
+        let mut out = String::from("// Generated by stringc, do not edit.\n\n");
+        if formatted {
+            out.push_str("use self::internal::Piece;\n\n");
+        }
+        out.push_str(&lang_enum(&langs, default_index)?);
+
+        let translations: Vec<BTreeMap<&str, &TranslationOut>> = self.strings.iter()
+            .map(|(_, translations)| translations.iter().map(|t| (t.key.as_str(), t)).collect())
+            .collect();
+
+        for (name, source) in unique_names(sources, "strings.rs", rust_identifier)? {
+            let values: Vec<&TranslationOut> = translations.iter()
+                .map(|t| t.get(source.key.as_str()).copied().filter(|t| !t.missing).unwrap_or(source))
+                .collect();
+            out.push('\n');
+            out.push_str(&accessor(&name, source, &langs, &values)?);
+        }
+
+        if plural {
+            out.push('\n');
+            out.push_str(&plural_index(&langs));
+        }
+        if formatted {
+            out.push('\n');
+            out.push_str(INTERNAL_MODULE);
+        }
+
+        Ok(out)
+    }
+}
+
+fn lang_enum(langs: &[&str], default_index: usize) -> std::io::Result<String> {
+    let mut variants: Vec<String> = vec![];
+
+    for lang in langs {
+        let variant = lang_variant(lang);
+        if variants.contains(&variant) {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("language \"{}\" is named \"{}\" like another one in strings.rs", lang, variant)));
+        }
+        variants.push(variant);
+    }
+
+    let all: Vec<String> = variants.iter().map(|v| format!("Lang::{}", v)).collect();
+    let codes: Vec<String> = langs.iter().map(|lang| format!("{:?}", lang)).collect();
+
+    Ok(format!("#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\npub enum Lang {{\n    {}\n}}\n\n\
+                impl Lang {{\n    \
+                    pub const ALL: [Lang; {count}] = [{all}];\n    \
+                    pub const DEFAULT: Lang = {default};\n    \
+                    const CODES: [&'static str; {count}] = [{codes}];\n\n    \
+                    /// Language code, e.g. `{example}`.\n    \
+                    pub fn code(self) -> &'static str {{\n        Lang::CODES[self as usize]\n    }}\n\n    \
+                    pub fn from_code(code: &str) -> Option<Lang> {{\n        \
+                        Lang::ALL.iter().copied().find(|lang| lang.code() == code)\n    }}\n\
+                }}\n",
+               variants.join(",\n    "), count = langs.len(), all = all.join(", "),
+               default = all[default_index], codes = codes.join(", "), example = langs[default_index]))
+}
+
+/// Static table of the key values and function reading it. Keys without placeholders return
+/// `&'static str`, plural keys take the count which selects the plural form. Translations with
+/// placeholders the default language value does not have are replaced with the default one.
+fn accessor(name: &str, source: &TranslationOut, langs: &[&str], values: &[&TranslationOut])
+            -> std::io::Result<String> {
+    let table = name.to_uppercase();
+    let plural = !source.plurals.is_empty();
+    let count_index = if plural { count_index(&source.value) } else { None };
+
+    let mut out: String = source.value.lines().map(|line| format!("/// {}", line).trim_end().to_string() + "\n").collect();
+    let placeholders = parse_placeholders(&source.value);
+
+    if !plural && placeholders.is_empty() {
+        let texts: Vec<String> = values.iter().map(|t| format!("{:?}", t.value.replace("%%", "%"))).collect();
+        out.push_str(&format!("pub fn {}(lang: Lang) -> &'static str {{\n    {}[lang as usize]\n}}\n\n", name, table));
+        out.push_str(&format!("static {}: [&str; {}] = [{}];\n", table, values.len(), texts.join(", ")));
+        return Ok(out);
+    }
+
+    let arguments: BTreeMap<usize, (String, PlaceholderType)> = placeholders.iter()
+        .map(|p| (p.index, (argument_name(p, count_index), p.kind)))
+        .collect();
+
+    let mut parameters = vec!["lang: Lang".to_string()];
+    if plural && count_index.is_none() {
+        parameters.push(format!("{}: i64", COUNT_ARGUMENT));
+    }
+    parameters.extend(arguments.values().map(|(name, kind)| format!("{}: {}", name, rust_type(*kind))));
+
+    let last_index = arguments.keys().last().copied().unwrap_or_default();
+    let fits = |translation: &TranslationOut| std::iter::once(&translation.value)
+        .chain(translation.plurals.values())
+        .all(|value| parse_placeholders(value).iter().all(|p| p.index <= last_index));
+
+    if !fits(source) {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              format!("plural forms of key \"{}\" have placeholders missing in its value", source.key)));
+    }
+    let values: Vec<&TranslationOut> = values.iter().map(|&t| if fits(t) { t } else { source }).collect();
+    let args: Vec<String> = (1..=last_index)
+        .map(|index| arguments.get(&index).map(|(name, _)| format!("&{}", name)).unwrap_or_else(|| "&\"\"".to_string()))
+        .collect();
+
+    if plural {
+        let forms: Vec<String> = langs.iter().zip(&values)
+            .map(|(lang, translation)| {
+                let forms: Vec<String> = plural_forms(lang).categories.iter()
+                    .map(|category| {
+                        let value = translation.plurals.get(*category)
+                            .or_else(|| translation.plurals.get("other"))
+                            .unwrap_or(&translation.value);
+                        pieces(value)
+                    })
+                    .collect();
+                format!("&[{}]", forms.join(", "))
+            })
+            .collect();
+
+        out.push_str(&format!("pub fn {}({}) -> String {{\n    \
+                                   let forms = {}[lang as usize];\n    \
+                                   internal::format(forms[plural_index(lang, {}.unsigned_abs())], &[{}])\n}}\n\n",
+                              name, parameters.join(", "), table, COUNT_ARGUMENT, args.join(", ")));
+        out.push_str(&format!("static {}: [&[&[Piece]]; {}] = [{}];\n", table, values.len(), forms.join(", ")));
+    } else {
+        let forms: Vec<String> = values.iter().map(|t| pieces(&t.value)).collect();
+
+        out.push_str(&format!("pub fn {}({}) -> String {{\n    internal::format({}[lang as usize], &[{}])\n}}\n\n",
+                              name, parameters.join(", "), table, args.join(", ")));
+        out.push_str(&format!("static {}: [&[Piece]; {}] = [{}];\n", table, values.len(), forms.join(", ")));
+    }
+
+    Ok(out)
+}
+
+/// Pieces of the value, placeholders become arguments with zero based index.
+fn pieces(value: &str) -> String {
+    let mut pieces = vec![];
+    let mut last = 0;
+
+    for placeholder in parse_placeholders(value) {
+        let text = value[last..placeholder.range.start].replace("%%", "%");
+        if !text.is_empty() {
+            pieces.push(format!("Piece::Text({:?})", text));
+        }
+
+        let spec = &value[placeholder.range.clone()];
+        let precision = spec.find('.')
+            .map(|idx| spec[idx + 1..].chars().take_while(|c| c.is_ascii_digit()).collect::<String>())
+            .filter(|_| placeholder.kind == PlaceholderType::Double);
+
+        match precision {
+            Some(precision) => pieces.push(format!("Piece::Precision({}, {})", placeholder.index - 1,
+                                                   precision.parse::<usize>().unwrap_or_default())),
+            None => pieces.push(format!("Piece::Arg({})", placeholder.index - 1))
+        }
+        last = placeholder.range.end;
+    }
+
+    let text = value[last..].replace("%%", "%");
+    if !text.is_empty() {
+        pieces.push(format!("Piece::Text({:?})", text));
+    }
+
+    format!("&[{}]", pieces.join(", "))
+}
+
+/// Plural form index of the count, gettext plural rules of the languages converted to Rust.
+fn plural_index(langs: &[&str]) -> String {
+    let arms: Vec<String> = langs.iter()
+        .map(|lang| format!("        Lang::{} => {}", lang_variant(lang), rust_expression(plural_forms(lang).expression)))
+        .collect();
+
+    format!("#[allow(unused_variables, clippy::manual_range_contains)]\nfn plural_index(lang: Lang, n: u64) -> usize {{\n    \
+                 match lang {{\n{}\n    }}\n}}\n", arms.join(",\n"))
+}
+
+/// Converts C plural expression to Rust, conditional operators become `if else` chains and
+/// comparisons are cast to index.
+fn rust_expression(expression: &str) -> String {
+    let expression = strip_parens(expression.trim());
+
+    let conditional = top_level_position(expression, '?')
+        .and_then(|question| {
+            let branches = &expression[question + 1..];
+            top_level_position(branches, ':').map(|colon| (&expression[..question], &branches[..colon], &branches[colon + 1..]))
+        });
+
+    match conditional {
+        Some((condition, then, otherwise)) => {
+            let otherwise = rust_expression(otherwise);
+            let otherwise = if otherwise.starts_with("if ") { otherwise } else { format!("{{ {} }}", otherwise) };
+
+            format!("if {} {{ {} }} else {}", strip_parens(condition.trim()), rust_expression(then), otherwise)
+        }
+        None if expression.chars().all(|c| c.is_ascii_digit()) => expression.to_string(),
+        None => format!("({}) as usize", expression)
+    }
+}
+
+fn strip_parens(expression: &str) -> &str {
+    if expression.starts_with('(') && top_level_position(&expression[1..], ')') == Some(expression.len() - 2) {
+        strip_parens(expression[1..expression.len() - 1].trim())
+    } else {
+        expression
+    }
+}
+
+/// Position of the character outside of parentheses.
+fn top_level_position(text: &str, target: char) -> Option<usize> {
+    let mut depth = 0;
+
+    for (idx, c) in text.char_indices() {
+        if c == target && depth == 0 {
+            return Some(idx);
+        }
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Converts key to snake case function name, e.g. `app.mainTitle` to `app_maintitle`. Names
+/// starting with a digit get a `key_` prefix, `_` is appended to keywords and generated names.
+fn rust_identifier(key: &str) -> String {
+    let mut identifier: String = key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier = format!("key_{}", identifier);
+    }
+    if RUST_KEYWORDS.contains(&identifier.as_str()) || GENERATED_NAMES.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+
+    identifier
+}
+
+/// Enum variant of the language, e.g. `EnUs` for `en-US`.
+fn lang_variant(lang: &str) -> String {
+    lang.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap_or_default();
+            format!("{}{}", first.to_ascii_uppercase(), chars.as_str().to_ascii_lowercase())
+        })
+        .collect()
+}
+
+fn rust_type(kind: PlaceholderType) -> &'static str {
+    match kind {
+        PlaceholderType::Text => "&str",
+        PlaceholderType::Int => "i64",
+        PlaceholderType::Double => "f64"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rust_generator::{lang_variant, pieces, rust_expression, rust_identifier, TranslationsRust};
+    use crate::strings_generator::TranslationOut;
+
+    #[test]
+    fn convert_names() {
+        assert_eq!("app_main_title", rust_identifier("app.main-title"));
+        assert_eq!("key_1st", rust_identifier("1st"));
+        assert_eq!("type_", rust_identifier("type"));
+        assert_eq!("plural_index_", rust_identifier("plural.index"));
+        assert_eq!("EnUs", lang_variant("en-US"));
+        assert_eq!("ZhHantTw", lang_variant("zh-Hant-TW"));
+    }
+
+    #[test]
+    fn convert_plural_expressions() {
+        assert_eq!("0", rust_expression("0"));
+        assert_eq!("(n != 1) as usize", rust_expression("(n != 1)"));
+        assert_eq!("if n==1 { 0 } else if n>=2 && n<=4 { 1 } else { 2 }",
+                   rust_expression("(n==1) ? 0 : (n>=2 && n<=4) ? 1 : 2"));
+        assert_eq!("if n==1 { 0 } else if n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) { 1 } else { 2 }",
+                   rust_expression("(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)"));
+    }
+
+    #[test]
+    fn split_values_to_pieces() {
+        assert_eq!("&[Piece::Text(\"Hello \\\"\"), Piece::Arg(1), Piece::Text(\"\\\" \"), Piece::Precision(0, 2), \
+                    Piece::Text(\"%\")]", pieces("Hello \"%2$s\" %1$.2f%%"));
+    }
+
+    #[test]
+    fn generate_rust_module() {
+        let mut files = TranslationOut::new("app.files", "%d files in %s");
+        files.plurals.insert("one".to_string(), "%d file in %s".to_string());
+        files.plurals.insert("other".to_string(), "%d files in %s".to_string());
+        let mut files_pl = TranslationOut::new("app.files", "%d plików w %s");
+        files_pl.plurals.insert("one".to_string(), "%d plik w %s".to_string());
+        files_pl.plurals.insert("few".to_string(), "%d pliki w %s".to_string());
+        files_pl.plurals.insert("other".to_string(), "%d plików w %s".to_string());
+        let mut title_pl = TranslationOut::new("app.title", "app.title");
+        title_pl.missing = true;
+
+        let generator = TranslationsRust {
+            default_lang: "en-US".to_string(),
+            strings: vec![
+                ("en-US".to_string(), vec![files, TranslationOut::new("app.title", "Title")]),
+                ("pl-PL".to_string(), vec![files_pl, title_pl])
+            ]
+        };
+        let source = generator.source().unwrap();
+
+        assert!(source.contains("pub enum Lang {\n    EnUs,\n    PlPl\n}\n"));
+        assert!(source.contains("pub const DEFAULT: Lang = Lang::EnUs;\n"));
+        assert!(source.contains("/// %d files in %s\npub fn app_files(lang: Lang, count: i64, arg2: &str) -> String {\n    \
+                                 let forms = APP_FILES[lang as usize];\n    \
+                                 internal::format(forms[plural_index(lang, count.unsigned_abs())], &[&count, &arg2])\n}\n"));
+        assert!(source.contains("static APP_FILES: [&[&[Piece]]; 2] = [\
+                                 &[&[Piece::Arg(0), Piece::Text(\" file in \"), Piece::Arg(1)], \
+                                 &[Piece::Arg(0), Piece::Text(\" files in \"), Piece::Arg(1)]], \
+                                 &[&[Piece::Arg(0), Piece::Text(\" plik w \"), Piece::Arg(1)], \
+                                 &[Piece::Arg(0), Piece::Text(\" pliki w \"), Piece::Arg(1)], \
+                                 &[Piece::Arg(0), Piece::Text(\" plików w \"), Piece::Arg(1)]]];\n"));
+        assert!(source.contains("use self::internal::Piece;\n"));
+        assert!(source.contains("fn plural_index(lang: Lang, n: u64) -> usize {\n"));
+        assert!(source.contains("/// Title\npub fn app_title(lang: Lang) -> &'static str {\n    APP_TITLE[lang as usize]\n}\n\n\
+                                 static APP_TITLE: [&str; 2] = [\"Title\", \"Title\"];\n"));
+    }
+
+    #[test]
+    fn generate_rust_module_with_extra_placeholders() {
+        let mut items = TranslationOut::new("app.items", "Items");
+        items.plurals.insert("other".to_string(), "Items".to_string());
+        let mut items_pl = TranslationOut::new("app.items", "%d elementów");
+        items_pl.plurals.insert("other".to_string(), "%d elementów".to_string());
+
+        let generator = TranslationsRust {
+            default_lang: "en-US".to_string(),
+            strings: vec![
+                ("en-US".to_string(), vec![TranslationOut::new("app.hello", "Hello %s"), items]),
+                ("pl-PL".to_string(), vec![TranslationOut::new("app.hello", "Witaj %s, %d"), items_pl])
+            ]
+        };
+        let source = generator.source().unwrap();
+
+        assert!(source.contains("static APP_HELLO: [&[Piece]; 2] = [\
+                                 &[Piece::Text(\"Hello \"), Piece::Arg(0)], &[Piece::Text(\"Hello \"), Piece::Arg(0)]];\n"));
+        assert!(source.contains("static APP_ITEMS: [&[&[Piece]]; 2] = [\
+                                 &[&[Piece::Text(\"Items\")], &[Piece::Text(\"Items\")]], \
+                                 &[&[Piece::Text(\"Items\")], &[Piece::Text(\"Items\")], &[Piece::Text(\"Items\")]]];\n"));
+
+        let mut files = TranslationOut::new("app.files", "Files");
+        files.plurals.insert("one".to_string(), "%d file".to_string());
+        let generator = TranslationsRust {
+            default_lang: "en-US".to_string(),
+            strings: vec![("en-US".to_string(), vec![files])]
+        };
+        assert!(generator.source().is_err());
+    }
+    #[test]
+    fn generate_rust_module_without_placeholders() {
+        let generator = TranslationsRust {
+            default_lang: "en-US".to_string(),
+            strings: vec![(
+                "en-US".to_string(),
+                vec![TranslationOut::new("app.title", "Title"), TranslationOut::new("app.items", "%0$d")]
+            )]
+        };
+        let source = generator.source().unwrap();
+
+        assert!(source.contains("static APP_ITEMS: [&str; 1] = [\"%0$d\"];\n"));
+        assert!(!source.contains("Piece"));
+        assert!(!source.contains("plural_index"));
+        assert!(!source.contains("mod internal"));
+    }
+}
@@ -1,6 +1,24 @@
-use crate::json_data::{DataRootTranslations, Project};
-use std::collections::BTreeMap;
+use crate::json_data::{DataRoot, DataRootTranslations, Project, inherited_project, project_chain};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Error, ErrorKind};
 use crate::ios_generator::TranslationsIOS;
+use crate::swift_generator::TranslationsSwift;
+use crate::xcstrings_generator::TranslationsXcStrings;
+use crate::xliff_generator::{TranslationsXliff, XliffVersion};
+use crate::po_generator::TranslationsPo;
+use crate::mo_generator::TranslationsMo;
+use crate::arb_generator::TranslationsArb;
+use crate::json_generator::TranslationsJson;
+use crate::properties_generator::TranslationsProperties;
+use crate::resx_generator::TranslationsResx;
+use crate::ts_generator::TranslationsTs;
+use crate::yaml_generator::TranslationsYaml;
+use crate::webext_generator::{check_locales, TranslationsWebExt};
+use crate::fluent_generator::TranslationsFluent;
+use crate::android_generator::TranslationsAndroid;
+use crate::kotlin_generator::TranslationsKotlin;
+use crate::typescript_generator::TranslationsTypeScript;
+use crate::rust_generator::TranslationsRust;
 
 pub trait Generator {
     fn generate(&self) -> std::io::Result<()>;
@@ -9,45 +27,316 @@ pub trait Generator {
 #[derive(Clone)]
 pub enum StringsGeneratorType {
     Ios,
-    Android
+    /// Android resources, with Kotlin accessors when the app package is set.
+    Android { package: Option<String> },
+    XcStrings,
+    Xliff(XliffVersion),
+    Po,
+    Mo,
+    Arb,
+    Json { nested: bool },
+    Properties { utf8: bool },
+    Resx,
+    Ts,
+    Yaml,
+    WebExt,
+    Fluent,
+    TypeScript,
+    Rust
 }
 
+#[derive(Clone)]
 pub struct TranslationOut {
     pub key: String,
-    pub value: String
+    pub value: String,
+    pub comment: Option<String>,
+    /// Plural forms by category, empty for keys without plurals.
+    pub plurals: BTreeMap<String, String>,
+    /// Translation was made from an older default language value.
+    pub outdated: bool,
+    /// There is no value in language, key name is used instead.
+    pub missing: bool
 }
 
-pub fn generate_strings(export_type: StringsGeneratorType, data: &DataRootTranslations, project: &Project) {
-    for lang in &project.langs {
-        let strings = prepare_strings(&data, &project.id, lang.to_string());
+/// Translations with the names `name` gives their keys. Keys which get the same name are an error,
+/// as their entries in `file_name` would clash.
+pub(crate) fn unique_names<'a>(translations: impl IntoIterator<Item = &'a TranslationOut>, file_name: &str,
+                               name: impl Fn(&str) -> String) -> std::io::Result<Vec<(String, &'a TranslationOut)>> {
+    let mut keys: BTreeMap<String, &str> = BTreeMap::new();
+    let mut named = vec![];
+
+    for translation in translations {
+        let translation_name = name(&translation.key);
+
+        if let Some(other_key) = keys.insert(translation_name.to_string(), &translation.key) {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("keys \"{}\" and \"{}\" are both named \"{}\" in {}",
+                                          other_key, translation.key, translation_name, file_name)));
+        }
+
+        named.push((translation_name, translation));
+    }
+
+    Ok(named)
+}
+
+#[cfg(test)]
+impl TranslationOut {
+    /// Up to date translation with value only, for generator tests.
+    pub(crate) fn new(key: &str, value: &str) -> TranslationOut {
+        TranslationOut {
+            key: key.to_string(),
+            value: value.to_string(),
+            comment: None,
+            plurals: BTreeMap::new(),
+            outdated: false,
+            missing: false
+        }
+    }
+}
+
+pub fn generate_strings(export_type: StringsGeneratorType, data_root: &DataRoot, project: &Project)
+                        -> std::io::Result<()> {
+    let strings = project_strings(data_root, project);
+
+    match export_type {
+        StringsGeneratorType::Ios => {
+            let accessors = TranslationsSwift { sources: default_lang_strings(&strings, project) };
+            accessors.generate()?;
 
-        match export_type {
-            StringsGeneratorType::Ios => {
-                let ios_generator = TranslationsIOS { lang: lang.to_string(), translations: strings };
-                ios_generator.generate();
+            for (lang, translations) in strings {
+                let ios_generator = TranslationsIOS { lang, translations };
+                ios_generator.generate()?;
             }
-            StringsGeneratorType::Android => {}
         }
+        StringsGeneratorType::Android { package } => {
+            if let Some(package) = package {
+                let accessors = TranslationsKotlin { package, sources: default_lang_strings(&strings, project) };
+                accessors.generate()?;
+            }
+
+            for (lang, translations) in android_strings(strings, project) {
+                let lang = if lang.eq(&project.default_lang) { None } else { Some(lang) };
+                let generator = TranslationsAndroid { lang, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::XcStrings => {
+            let generator = TranslationsXcStrings { source_lang: project.default_lang.to_string(), strings };
+            generator.generate()?;
+        }
+        StringsGeneratorType::Xliff(version) => {
+            let sources: BTreeMap<String, TranslationOut> = strings.iter()
+                .filter(|(lang, _)| lang.eq(&project.default_lang))
+                .flat_map(|(_, translations)| translations.iter())
+                .filter(|t| !t.missing)
+                .map(|t| (t.key.to_string(), t.clone()))
+                .collect();
+
+            for (target_lang, translations) in strings {
+                if target_lang.eq(&project.default_lang) {
+                    continue;
+                }
+
+                let generator = TranslationsXliff {
+                    version: version.clone(),
+                    project_name: project.name.to_string(),
+                    source_lang: project.default_lang.to_string(),
+                    target_lang,
+                    sources: sources.clone(),
+                    translations
+                };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Po => {
+            let sources = default_lang_strings(&strings, project);
+
+            let template = TranslationsPo {
+                project_name: project.name.to_string(),
+                lang: None,
+                sources: sources.to_vec(),
+                translations: vec![]
+            };
+            template.generate()?;
+
+            for (lang, translations) in strings {
+                let generator = TranslationsPo {
+                    project_name: project.name.to_string(),
+                    lang: Some(lang),
+                    sources: sources.to_vec(),
+                    translations
+                };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Mo => {
+            let sources = default_lang_strings(&strings, project);
+
+            for (lang, translations) in strings {
+                let generator = TranslationsMo {
+                    project_name: project.name.to_string(),
+                    lang,
+                    sources: sources.to_vec(),
+                    translations
+                };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Arb => {
+            for (lang, translations) in strings {
+                let metadata = lang.eq(&project.default_lang);
+                let generator = TranslationsArb { lang, metadata, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Json { nested } => {
+            for (lang, translations) in strings {
+                let generator = TranslationsJson { lang, nested, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Properties { utf8 } => {
+            for (lang, translations) in strings {
+                let generator = TranslationsProperties { lang, utf8, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Resx => {
+            for (lang, translations) in strings {
+                let lang = if lang.eq(&project.default_lang) { None } else { Some(lang) };
+                let generator = TranslationsResx { lang, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Ts => {
+            let sources = default_lang_strings(&strings, project);
+
+            for (lang, translations) in strings {
+                if lang.eq(&project.default_lang) {
+                    continue;
+                }
+
+                let generator = TranslationsTs {
+                    lang,
+                    source_lang: project.default_lang.to_string(),
+                    sources: sources.to_vec(),
+                    translations
+                };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Yaml => {
+            for (lang, translations) in strings {
+                let generator = TranslationsYaml { lang, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::WebExt => {
+            let langs: Vec<&str> = strings.iter().map(|(lang, _)| lang.as_str()).collect();
+            check_locales(&langs)?;
+
+            for (lang, translations) in strings {
+                let generator = TranslationsWebExt { lang, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::Fluent => {
+            for (lang, translations) in strings {
+                let generator = TranslationsFluent { lang, translations };
+                generator.generate()?;
+            }
+        }
+        StringsGeneratorType::TypeScript => {
+            let generator = TranslationsTypeScript { sources: default_lang_strings(&strings, project) };
+            generator.generate()?;
+        }
+        StringsGeneratorType::Rust => {
+            let generator = TranslationsRust { default_lang: project.default_lang.to_string(), strings };
+            generator.generate()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Prepared strings of every project language, see [`prepare_project_strings`].
+pub(crate) fn project_strings(data_root: &DataRoot, project: &Project) -> Vec<(String, Vec<TranslationOut>)> {
+    inherited_project(&data_root.projects, project).langs.iter()
+        .map(|lang| (lang.to_string(), prepare_project_strings(data_root, project, lang)))
+        .collect()
+}
+
+/// Strings without keys missing in the default language, resources which are only in
+/// `values-xx` make the app crash in the other locales.
+fn android_strings(strings: Vec<(String, Vec<TranslationOut>)>, project: &Project)
+                   -> Vec<(String, Vec<TranslationOut>)> {
+    let keys: BTreeSet<String> = default_lang_strings(&strings, project).into_iter()
+        .filter(|t| !t.missing)
+        .map(|t| t.key)
+        .collect();
+
+    strings.into_iter()
+        .map(|(lang, translations)| {
+            (lang, translations.into_iter().filter(|t| keys.contains(&t.key)).collect())
+        })
+        .collect()
+}
+
+fn default_lang_strings(strings: &[(String, Vec<TranslationOut>)], project: &Project) -> Vec<TranslationOut> {
+    strings.iter()
+        .find(|(lang, _)| lang.eq(&project.default_lang))
+        .map(|(_, translations)| translations.to_vec())
+        .unwrap_or_default()
+}
+
+/// Prepares project strings in language, see [`prepare_strings`], with outdated translations
+/// marked.
+pub fn prepare_project_strings(data_root: &DataRoot, project: &Project, lang: &str) -> Vec<TranslationOut> {
+    let chain = project_chain(&data_root.projects, project);
+    let project = inherited_project(&data_root.projects, project);
+    let mut strings = prepare_strings(&data_root.translations, &chain, lang.to_string());
+
+    for translation_out in strings.iter_mut() {
+        translation_out.outdated = data_root.translations.get(&translation_out.key)
+            .is_some_and(|t| t.is_outdated(&project, &chain, lang));
     }
+
+    strings
 }
 
-fn prepare_strings(data: &DataRootTranslations, project_id: &u16, lang: String) -> Vec<TranslationOut> {
+/// Prepares project strings in language. Values are taken from the first project in
+/// `project_chain` which has them, then from shared values, key name is used as a fallback.
+pub fn prepare_strings(data: &DataRootTranslations, project_chain: &[u16], lang: String) -> Vec<TranslationOut> {
     let mut translations: Vec<TranslationOut> = vec![];
 
     let items: BTreeMap<_, _> = data.iter()
-        .filter(|&p| p.1.projects.contains(&project_id))
+        .filter(|&p| p.1.in_projects(project_chain))
         .collect();
 
     for item in items {
-        let mut value: &str = item.0.as_str();
+        let resolved = item.1.resolve_in(project_chain, &lang);
+        let value = resolved
+            .map(|v| v.as_str())
+            .unwrap_or_else(|| item.0.as_str());
 
-        if let Some(k) = item.1.values.get(&project_id) {
-            if let Some(t) = k.get(&lang) {
-                value = t.as_str();
-            }
+        let mut plurals = item.1.plurals_in(project_chain, &lang)
+            .cloned()
+            .unwrap_or_default();
+
+        if !plurals.is_empty() {
+            plurals.entry("other".to_string())
+                .or_insert_with(|| value.to_string());
         }
 
-        let translation = TranslationOut { key: item.0.to_string(), value: value.to_string()};
+        let translation = TranslationOut {
+            key: item.0.to_string(),
+            value: value.to_string(),
+            comment: item.1.comment.clone(),
+            plurals,
+            outdated: false,
+            missing: resolved.is_none()
+        };
         translations.push(translation);
     }
 
@@ -56,14 +345,26 @@ fn prepare_strings(data: &DataRootTranslations, project_id: &u16, lang: String)
 
 #[cfg(test)]
 mod tests {
-    use crate::json_data::{get_projects, DataRoot};
-    use crate::strings_generator::prepare_strings;
-    use crate::strings_generator::StringsGeneratorType::Ios;
+    use crate::json_data::{get_projects, project_chain, DataRoot};
+    use crate::strings_generator::{android_strings, prepare_strings, project_strings, unique_names, TranslationOut};
+
+    #[test]
+    fn name_translations_uniquely() {
+        let translations = vec![TranslationOut::new("app.title", "Title"), TranslationOut::new("app.name", "Name")];
+        let named = unique_names(&translations, "strings.xml", |key| key.replace('.', "_")).unwrap();
+        let names: Vec<&str> = named.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(vec!["app_title", "app_name"], names);
+
+        let clashing = vec![TranslationOut::new("app.title", "Title"), TranslationOut::new("app_title", "Title")];
+        let error = unique_names(&clashing, "strings.xml", |key| key.replace('.', "_")).err().unwrap();
+        assert_eq!("keys \"app.title\" and \"app_title\" are both named \"app_title\" in strings.xml",
+                   error.to_string());
+    }
 
     #[test]
     fn export_ios() {
         let data = generate_basic_data();
-        let strings = prepare_strings(&data.translations, &data.projects[0].id, "en-US".to_string());
+        let strings = prepare_strings(&data.translations, &[data.projects[0].id], "en-US".to_string());
 
         assert_eq!(strings.len(), 3);
         assert_eq!(strings[0].key, "app.hello");
@@ -71,87 +372,96 @@ mod tests {
         assert_eq!(strings[2].value, "app.t3");
     }
 
-    fn generate_basic_data() -> DataRoot {
+    #[test]
+    fn export_shared_values() {
         let json = r#"
     {
       "projects": [
-        {
-          "id": 1,
-          "name": "TestProject1",
-          "langs": [
-            "en-US",
-            "pl-PL"
-          ],
-          "defaultLang": "en-US"
-        },
-        {
-          "id": 2,
-          "name": "TestProject2",
-          "langs": [
-            "en-US",
-            "de-DE"
-          ],
-          "defaultLang": "en-US"
+        { "id": 1, "name": "Brand1", "langs": ["en-US", "pl-PL"], "defaultLang": "en-US" },
+        { "id": 2, "name": "Brand2", "langs": ["en-US", "pl-PL"], "defaultLang": "en-US" }
+      ],
+      "translations": {
+        "app.title": {
+          "projects": [1, 2],
+          "values": { "2": { "en-US": "Brand2 app" } },
+          "shared": { "en-US": "App", "pl-PL": "Aplikacja" }
         }
+      }
+    }
+    "#;
+        let data = get_projects(json).expect("Error parsing json");
+
+        let strings = prepare_strings(&data.translations, &[1], "en-US".to_string());
+        assert_eq!(strings[0].value, "App");
+
+        let strings = prepare_strings(&data.translations, &[2], "en-US".to_string());
+        assert_eq!(strings[0].value, "Brand2 app");
+
+        let strings = prepare_strings(&data.translations, &[2], "pl-PL".to_string());
+        assert_eq!(strings[0].value, "Aplikacja");
+    }
+
+    #[test]
+    fn export_inherited_values() {
+        let json = r#"
+    {
+      "projects": [
+        { "id": 1, "name": "App", "langs": ["en-US", "pl-PL"], "defaultLang": "en-US" },
+        { "id": 2, "name": "Brand", "langs": [], "defaultLang": "en-US", "parent": 1 }
       ],
       "translations": {
-        "app.hello": {
-          "projects": [
-            1,
-            2
-          ],
-          "values": {
-            "1": {
-              "en-US": "Hello",
-              "pl-PL": "Witaj"
-            },
-            "2": {
-              "en-US": "Hello",
-              "de-DE": "Hallo"
-            }
-          }
-        },
-        "app.t1": {
-          "projects": [
-            1,
-            2
-          ],
-          "values": {
-            "1": {
-              "en-US": "test1-en-p1",
-              "pl-PL": "test1-pl-p1"
-            },
-            "2": {
-              "en-US": "test1-en-proj2",
-              "de-DE": "test1-de-proj2"
-            }
-          }
+        "app.title": {
+          "projects": [1],
+          "values": { "1": { "en-US": "App", "pl-PL": "Aplikacja" }, "2": { "en-US": "Brand app" } }
         },
-        "app.t2": {
-          "projects": [
-            2
-          ],
-          "values": {
-            "2": {
-              "en-US": "test2-en-proj2",
-              "de-DE": "test2-de-proj2"
-            }
-          }
+        "app.hello": {
+          "projects": [1],
+          "values": { "1": { "en-US": "Hello" } }
+        }
+      }
+    }
+    "#;
+        let data = get_projects(json).expect("Error parsing json");
+        let chain = project_chain(&data.projects, &data.projects[1]);
+
+        let strings = prepare_strings(&data.translations, &chain, "en-US".to_string());
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings[0].value, "Hello");
+        assert_eq!(strings[1].value, "Brand app");
+
+        let strings = prepare_strings(&data.translations, &chain, "pl-PL".to_string());
+        assert_eq!(strings[1].value, "Aplikacja");
+    }
+
+    #[test]
+    fn export_android_without_default_values() {
+        let json = r#"
+    {
+      "projects": [
+        { "id": 1, "name": "App", "langs": ["en-US", "pl-PL"], "defaultLang": "en-US" }
+      ],
+      "translations": {
+        "app.title": {
+          "projects": [1],
+          "values": { "1": { "en-US": "App", "pl-PL": "Aplikacja" } }
         },
-        "app.t3": {
-          "projects": [
-            1
-          ],
-          "values": {
-            "2": {
-              "en-US": "test3-en-proj1"
-            }
-          }
+        "app.hello": {
+          "projects": [1],
+          "values": { "1": { "pl-PL": "Witaj" } }
         }
       }
     }
     "#;
+        let data = get_projects(json).expect("Error parsing json");
 
-        get_projects(json).expect("Error parsing json")
+        let strings = android_strings(project_strings(&data, &data.projects[0]), &data.projects[0]);
+        let keys: Vec<(&str, &str)> = strings.iter()
+            .flat_map(|(lang, translations)| translations.iter().map(move |t| (lang.as_str(), t.key.as_str())))
+            .collect();
+        assert_eq!(vec![("en-US", "app.title"), ("pl-PL", "app.title")], keys);
+    }
+
+    fn generate_basic_data() -> DataRoot {
+        get_projects(include_str!("../tests/common/basic_data.json")).expect("Error parsing json")
     }
 }
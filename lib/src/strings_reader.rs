@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::encoding::{collect_paths, decode};
+use crate::excel_reader::{ImportResult, Importer};
+use crate::json_data::{DataRootTranslations, Project};
+use crate::placeholders::{parse_placeholders, PlaceholderType};
+use crate::po_generator::po_lang;
+
+/// Comment Xcode writes for keys without one.
+const EMPTY_COMMENT: &str = "No comment provided by engineer.";
+
+#[derive(Debug)]
+pub enum StringsError {
+    Io(std::io::Error),
+    Encoding(String),
+    Syntax(String, usize)
+}
+
+impl fmt::Display for StringsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringsError::Io(e) => write!(f, "cannot read strings file: {}", e),
+            StringsError::Encoding(name) => write!(f, "unknown encoding of \"{}\"", name),
+            StringsError::Syntax(name, line) => write!(f, "invalid strings file \"{}\" at line {}", name, line)
+        }
+    }
+}
+
+impl Error for StringsError {}
+
+impl From<std::io::Error> for StringsError {
+    fn from(e: std::io::Error) -> Self {
+        StringsError::Io(e)
+    }
+}
+
+type Result<T> = std::result::Result<T, StringsError>;
+
+/// Decoded `.strings` file with its project language.
+pub struct StringsFile {
+    pub name: String,
+    pub lang: String,
+    pub data: String
+}
+
+struct Entry {
+    key: String,
+    value: String,
+    comment: Option<String>
+}
+
+/// Reads `.strings` files of project languages from `.lproj` directories or named like the exported
+/// `Localized_<lang>.strings`, `path` is either a directory searched recursively or a single file.
+/// `Base.lproj` is read as the default language, files of languages not in project are skipped.
+pub fn read_strings_files(path: &Path, project: &Project) -> Result<Vec<StringsFile>> {
+    let mut paths = vec![];
+    collect_paths(path, "strings", &mut paths)?;
+    paths.sort();
+
+    let mut files = vec![];
+    for path in paths {
+        let lang = path.parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|dir| dir.to_str())
+            .and_then(|dir| dir.strip_suffix(".lproj"))
+            .or_else(|| path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.strip_prefix("Localized_")))
+            .and_then(|lproj| lproj_lang(lproj, project));
+
+        if let Some(lang) = lang {
+            let name = path.display().to_string();
+            let data = decode(&fs::read(&path)?).ok_or_else(|| StringsError::Encoding(name.to_string()))?;
+            files.push(StringsFile { name, lang, data });
+        }
+    }
+
+    Ok(files)
+}
+
+/// Imports `.strings` files into project, default language files first. Placeholders are converted
+/// to the canonical form, `%@` becomes `%d` when the default language value has an integer
+/// placeholder there. Comments are imported from the default language files only.
+pub fn import_strings(files: &[StringsFile], data_root: &mut DataRootTranslations, project: &Project,
+                      project_chain: &[u16], ignore_unknown: bool) -> Result<ImportResult> {
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
+
+    let mut files: Vec<&StringsFile> = files.iter().collect();
+    files.sort_by_key(|file| file.lang.ne(&project.default_lang));
+
+    for file in files {
+        let default_lang = file.lang.eq(&project.default_lang);
+
+        for entry in parse(&file.data).map_err(|line| StringsError::Syntax(file.name.to_string(), line))? {
+            let source = importer.data_root.get(&entry.key)
+                .and_then(|t| t.resolve_in(project_chain, &project.default_lang))
+                .cloned()
+                .unwrap_or_default();
+
+            importer.value(&entry.key, &file.lang, canonical_placeholders(&entry.value, &source));
+
+            if let Some(comment) = entry.comment.filter(|c| default_lang && c.ne(EMPTY_COMMENT)) {
+                if let Some(translation) = importer.translation(&entry.key) {
+                    translation.comment = Some(comment);
+                }
+            }
+        }
+    }
+
+    Ok(importer.result)
+}
+
+/// Project language of the `.lproj` directory, `Base.lproj` is the default language.
+fn lproj_lang(lproj: &str, project: &Project) -> Option<String> {
+    if lproj.eq("Base") {
+        Some(project.default_lang.to_string())
+    } else {
+        matching_lang(lproj, project)
+    }
+}
+
+/// Project language matching the language code, `pl` matches `pl-PL` unless the project has
+/// another language with the same language part, the default one is preferred then.
+pub(crate) fn matching_lang(code: &str, project: &Project) -> Option<String> {
+    let language = |lang: &str| po_lang(lang).split('_').next().unwrap_or_default().to_string();
+
+    project.langs.iter()
+        .find(|lang| po_lang(lang).eq(&po_lang(code)))
+        .or_else(|| {
+            if code.contains(['-', '_'].as_ref()) {
+                return None;
+            }
+
+            let mut langs = project.langs.iter().filter(|lang| language(lang).eq(code)).peekable();
+            let first = langs.peek().copied();
+            langs.find(|lang| lang.eq(&&project.default_lang)).or(first)
+        })
+        .cloned()
+}
+
+/// Converts iOS placeholders to the canonical form, `%@` to `%s` or `%d` depending on the source
+/// placeholder and `%lld` to `%d`.
+fn canonical_placeholders(value: &str, source: &str) -> String {
+    let kinds: BTreeMap<usize, PlaceholderType> = parse_placeholders(source).into_iter()
+        .map(|p| (p.index, p.kind))
+        .collect();
+
+    let mut out = String::new();
+    let mut last = 0;
+
+    for placeholder in parse_placeholders(value) {
+        out.push_str(&value[last..placeholder.range.start]);
+
+        let spec = &value[placeholder.range.clone()];
+        let (body, conversion) = spec.split_at(spec.len() - 1);
+        out.extend(body.chars().filter(|c| !"hlqzjt".contains(*c)));
+        out.push_str(match conversion {
+            "@" if kinds.get(&placeholder.index) == Some(&PlaceholderType::Int) => "d",
+            "@" => "s",
+            other => other
+        });
+
+        last = placeholder.range.end;
+    }
+    out.push_str(&value[last..]);
+
+    out
+}
+
+/// Parses entries, returns the line of the first syntax error.
+fn parse(data: &str) -> std::result::Result<Vec<Entry>, usize> {
+    let mut parser = Parser { chars: data.chars().collect(), pos: 0, line: 1 };
+    let mut entries = vec![];
+
+    loop {
+        let comment = parser.skip_whitespace_and_comments()?;
+        if parser.peek().is_none() {
+            break;
+        }
+
+        let key = parser.string()?;
+        parser.skip_whitespace_and_comments()?;
+        parser.expect('=')?;
+        parser.skip_whitespace_and_comments()?;
+        let value = parser.string()?;
+        parser.skip_whitespace_and_comments()?;
+        parser.expect(';')?;
+
+        entries.push(Entry { key, value, comment });
+    }
+
+    Ok(entries)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> std::result::Result<(), usize> {
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.line)
+        }
+    }
+
+    /// Skips whitespace and comments, returns the last comment.
+    /// Skips whitespace and comments, returning the comment directly preceding the next token.
+    /// Comments following a token on the same line or separated by a blank line are dropped.
+    fn skip_whitespace_and_comments(&mut self) -> std::result::Result<Option<String>, usize> {
+        let mut comment = None;
+        let mut line_start = self.pos == 0;
+        let mut newlines = 0;
+
+        loop {
+            match (self.peek(), self.chars.get(self.pos + 1)) {
+                (Some(c), _) if c.is_whitespace() || c == '\u{feff}' => {
+                    if c == '\n' {
+                        line_start = true;
+                        newlines += 1;
+                        if newlines > 1 {
+                            comment = None;
+                        }
+                    }
+                    self.next();
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    let mut text = String::new();
+                    loop {
+                        match (self.next(), self.peek()) {
+                            (Some('*'), Some('/')) => {
+                                self.next();
+                                break;
+                            }
+                            (Some(c), _) => text.push(c),
+                            (None, _) => return Err(self.line)
+                        }
+                    }
+                    comment = Some(text.lines().map(|l| l.trim()).collect::<Vec<&str>>().join("\n").trim().to_string())
+                        .filter(|_| line_start);
+                    newlines = 0;
+                }
+                (Some('/'), Some('/')) => {
+                    let mut text = String::new();
+                    while let Some(c) = self.peek().filter(|c| *c != '\n') {
+                        text.push(c);
+                        self.next();
+                    }
+                    comment = Some(text[2..].trim().to_string()).filter(|_| line_start);
+                    newlines = 0;
+                }
+                _ => return Ok(comment)
+            }
+        }
+    }
+
+    /// Quoted string with escapes or unquoted one made of letters, digits and `_.$:/-`.
+    fn string(&mut self) -> std::result::Result<String, usize> {
+        if self.peek() != Some('"') {
+            let mut text = String::new();
+            while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || "_.$:/-".contains(*c)) {
+                text.push(c);
+                self.next();
+            }
+            return if text.is_empty() { Err(self.line) } else { Ok(text) };
+        }
+
+        self.next();
+        let mut units: Vec<u16> = vec![];
+        loop {
+            let c = self.next().ok_or(self.line)?;
+            match c {
+                '"' => break,
+                '\\' => match self.next().ok_or(self.line)? {
+                    'n' => units.push('\n' as u16),
+                    't' => units.push('\t' as u16),
+                    'r' => units.push('\r' as u16),
+                    'U' | 'u' => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        units.push(u16::from_str_radix(&hex, 16).map_err(|_| self.line)?);
+                    }
+                    d @ '0'..='7' => {
+                        let mut octal = d.to_string();
+                        while let Some(d) = self.peek().filter(|d| ('0'..='7').contains(d) && octal.len() < 3) {
+                            octal.push(d);
+                            self.next();
+                        }
+                        units.push(u16::from_str_radix(&octal, 8).map_err(|_| self.line)?);
+                    }
+                    other => units.extend(other.encode_utf16(&mut [0; 2]).iter())
+                },
+                other => units.extend(other.encode_utf16(&mut [0; 2]).iter())
+            }
+        }
+
+        String::from_utf16(&units).map_err(|_| self.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_reader::{canonical_placeholders, parse};
+
+    #[test]
+    fn parse_strings() {
+        let entries = parse("/* Greeting\n   on start */\n\"app.hello\" = \"Hello \\\"%@\\\"\\n\\U00e9\\UD83D\\UDE00\";\n\
+                             // Title\napp.title=\"Title\" ;\n\"app.t1\" = \"a\\\\b\";").unwrap();
+
+        assert_eq!(3, entries.len());
+        assert_eq!(("app.hello", "Hello \"%@\"\né😀"), (entries[0].key.as_str(), entries[0].value.as_str()));
+        assert_eq!(Some("Greeting\non start".to_string()), entries[0].comment);
+        assert_eq!(("app.title", "Title"), (entries[1].key.as_str(), entries[1].value.as_str()));
+        assert_eq!(Some("Title".to_string()), entries[1].comment);
+        assert_eq!("a\\b", entries[2].value);
+        assert_eq!(Some(2), parse("\"a\" = \"b\";\n\"c\" \"d\";").err());
+    }
+
+    #[test]
+    fn convert_placeholders() {
+        assert_eq!("%d files in %s, 100%%", canonical_placeholders("%@ files in %@, 100%%", "%d files in %s"));
+        assert_eq!("%2$s: %1$d", canonical_placeholders("%2$@: %1$lld", ""));
+    }
+}
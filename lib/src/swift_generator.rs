@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use crate::ios_generator::TranslationsIOS;
+use crate::placeholders::{parse_placeholders, PlaceholderType};
+use crate::strings_generator::{unique_names, Generator, TranslationOut};
+
+const SWIFT_KEYWORDS: [&str; 48] = [
+    "associatedtype", "as", "break", "case", "catch", "class", "continue", "default", "defer", "deinit", "do",
+    "else", "enum", "extension", "fallthrough", "false", "fileprivate", "for", "func", "guard", "if", "import",
+    "in", "init", "inout", "internal", "is", "let", "nil", "open", "operator", "private", "protocol", "public",
+    "repeat", "rethrows", "return", "self", "static", "struct", "subscript", "super", "switch", "throw",
+    "throws", "true", "try", "typealias"
+];
+
+/// Type names which can't be used for nested enums.
+const SWIFT_RESERVED_TYPES: [&str; 4] = ["Any", "Protocol", "Self", "Type"];
+
+/// Swift accessors for keys of the `.strings` files, written to `L10n.swift`. Dotted keys become
+/// nested enums in `L10n`, e.g. `L10n.App.title` for `app.title`. Keys with placeholders are
+/// functions with parameters typed after the default language placeholders.
+pub struct TranslationsSwift {
+    /// Default language strings.
+    pub sources: Vec<TranslationOut>
+}
+
+#[derive(Default)]
+struct Namespace<'a> {
+    namespaces: BTreeMap<String, Namespace<'a>>,
+    accessors: BTreeMap<String, &'a TranslationOut>
+}
+
+impl Generator for TranslationsSwift {
+    fn generate(&self) -> std::io::Result<()> {
+        let source = self.source()?;
+
+        let file = File::create("L10n.swift")?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(source.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsSwift {
+    /// Swift source of the accessors. Keys which map to the same accessor are an error.
+    fn source(&self) -> std::io::Result<String> {
+        let mut root = Namespace::default();
+        let sources = self.sources.iter().filter(|s| s.key.split('.').any(|segment| !segment.is_empty()));
+
+        for (_, source) in unique_names(sources, "L10n.swift", accessor_path)? {
+            let segments: Vec<&str> = source.key.split('.').filter(|s| !s.is_empty()).collect();
+            let (name, path) = match segments.split_last() {
+                Some((name, path)) => (swift_identifier(name, false), path),
+                None => continue
+            };
+
+            let namespace = path.iter()
+                .fold(&mut root, |namespace, segment| {
+                    namespace.namespaces.entry(swift_identifier(segment, true)).or_default()
+                });
+
+            namespace.accessors.insert(name, source);
+        }
+
+        let mut out = String::from("// Generated by stringc, do not edit.\n\nimport Foundation\n\n");
+        write_namespace(&mut out, "L10n", &root, 0);
+        out.push_str("\nextension L10n {\n    fileprivate static func tr(_ key: String, _ args: CVarArg...) -> String {\n        \
+                      let format = NSLocalizedString(key, comment: \"\")\n        \
+                      return String(format: format, locale: Locale.current, arguments: args)\n    \
+                      }\n}\n");
+
+        Ok(out)
+    }
+}
+
+fn write_namespace(out: &mut String, name: &str, namespace: &Namespace, level: usize) {
+    let indent = "    ".repeat(level);
+    out.push_str(&format!("{}enum {} {{\n", indent, name));
+
+    for (name, source) in &namespace.accessors {
+        write_accessor(out, name, source, level + 1);
+    }
+    for (name, child) in &namespace.namespaces {
+        write_namespace(out, name, child, level + 1);
+    }
+
+    out.push_str(&format!("{}}}\n", indent));
+}
+
+/// Static property for keys without placeholders, static function otherwise. Arguments are
+/// passed as text to the placeholders which are `%@` in the `.strings` files.
+fn write_accessor(out: &mut String, name: &str, source: &TranslationOut, level: usize) {
+    let indent = "    ".repeat(level);
+
+    for line in source.value.lines() {
+        out.push_str(format!("{}/// {}", indent, line).trim_end());
+        out.push('\n');
+    }
+
+    let parameters: BTreeMap<usize, PlaceholderType> = parse_placeholders(&source.value).into_iter()
+        .map(|p| (p.index, p.kind))
+        .collect();
+    let output_kinds: BTreeMap<usize, PlaceholderType> = parse_placeholders(&TranslationsIOS::escape(&source.value))
+        .into_iter()
+        .map(|p| (p.index, p.kind))
+        .collect();
+
+    let key = swift_string(&source.key);
+
+    if parameters.is_empty() {
+        out.push_str(&format!("{}static var {}: String {{\n{}    return L10n.tr({})\n{}}}\n",
+                              indent, name, indent, key, indent));
+        return;
+    }
+
+    let declarations: Vec<String> = parameters.iter()
+        .map(|(index, kind)| format!("_ arg{}: {}", index, swift_type(*kind)))
+        .collect();
+    let arguments: Vec<String> = parameters.iter()
+        .map(|(index, kind)| match output_kinds.get(index) {
+            Some(PlaceholderType::Text) if *kind != PlaceholderType::Text => format!("String(describing: arg{})", index),
+            _ => format!("arg{}", index)
+        })
+        .collect();
+
+    out.push_str(&format!("{}static func {}({}) -> String {{\n{}    return L10n.tr({}, {})\n{}}}\n",
+                          indent, name, declarations.join(", "), indent, key, arguments.join(", "), indent));
+}
+
+/// Accessor path in `L10n`, e.g. `App.title` for `app.title`.
+fn accessor_path(key: &str) -> String {
+    let segments: Vec<&str> = key.split('.').filter(|s| !s.is_empty()).collect();
+
+    segments.iter()
+        .enumerate()
+        .map(|(idx, segment)| swift_identifier(segment, idx + 1 < segments.len()))
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Converts key segment to camel case Swift identifier, upper camel case for type names.
+/// Identifiers starting with a digit get a `key` prefix, keywords are escaped with backticks.
+fn swift_identifier(segment: &str, type_name: bool) -> String {
+    let mut identifier = String::new();
+
+    for word in segment.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        let first = chars.next().unwrap_or_default();
+
+        if identifier.is_empty() && !type_name {
+            identifier.push(first.to_ascii_lowercase());
+        } else {
+            identifier.push(first.to_ascii_uppercase());
+        }
+        identifier.push_str(chars.as_str());
+    }
+
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier = format!("{}{}", if type_name { "Key" } else { "key" }, identifier);
+    }
+
+    if type_name && SWIFT_RESERVED_TYPES.contains(&identifier.as_str()) {
+        identifier.push('_');
+    } else if SWIFT_KEYWORDS.contains(&identifier.as_str()) {
+        identifier = format!("`{}`", identifier);
+    }
+
+    identifier
+}
+
+fn swift_type(kind: PlaceholderType) -> &'static str {
+    match kind {
+        PlaceholderType::Text => "String",
+        PlaceholderType::Int => "Int",
+        PlaceholderType::Double => "Double"
+    }
+}
+
+fn swift_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_generator::TranslationOut;
+    use crate::swift_generator::{swift_identifier, TranslationsSwift};
+
+    #[test]
+    fn convert_keys_to_identifiers() {
+        assert_eq!("mainTitle", swift_identifier("main_title", false));
+        assert_eq!("MainTitle", swift_identifier("main_title", true));
+        assert_eq!("key1st", swift_identifier("1st", false));
+        assert_eq!("`default`", swift_identifier("default", false));
+        assert_eq!("Type_", swift_identifier("type", true));
+    }
+
+    #[test]
+    fn generate_swift() {
+        let generator = TranslationsSwift {
+            sources: vec![
+                TranslationOut::new("title", "Title"),
+                TranslationOut::new("app.files", "%d files in %s, %.1f%%"),
+                TranslationOut::new("app.list.empty", "No files")
+            ]
+        };
+
+        assert_eq!("// Generated by stringc, do not edit.\n\nimport Foundation\n\n\
+                    enum L10n {\n    \
+                        /// Title\n    \
+                        static var title: String {\n        return L10n.tr(\"title\")\n    }\n    \
+                        enum App {\n        \
+                            /// %d files in %s, %.1f%%\n        \
+                            static func files(_ arg1: Int, _ arg2: String, _ arg3: Double) -> String {\n            \
+                                return L10n.tr(\"app.files\", String(describing: arg1), arg2, arg3)\n        \
+                            }\n        \
+                            enum List {\n            \
+                                /// No files\n            \
+                                static var empty: String {\n                return L10n.tr(\"app.list.empty\")\n            }\n        \
+                            }\n    \
+                        }\n\
+                    }\n\n\
+                    extension L10n {\n    \
+                        fileprivate static func tr(_ key: String, _ args: CVarArg...) -> String {\n        \
+                            let format = NSLocalizedString(key, comment: \"\")\n        \
+                            return String(format: format, locale: Locale.current, arguments: args)\n    \
+                        }\n\
+                    }\n", generator.source().unwrap());
+    }
+}
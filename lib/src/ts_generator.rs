@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use quick_xml::escape::escape;
+
+use crate::placeholders::{count_index, replace_placeholders};
+use crate::po_generator::{plural_forms, po_lang};
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// Qt Linguist translation source of a language. Messages are grouped in contexts by key prefix
+/// and identified by the key in `id`, plural keys are `numerus` messages. Placeholders are
+/// written as `%1`, `%2`, ... with the plural count as `%n`.
+pub struct TranslationsTs {
+    pub lang: String,
+    pub source_lang: String,
+    /// Default language strings.
+    pub sources: Vec<TranslationOut>,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsTs {
+    fn generate(&self) -> std::io::Result<()> {
+        let file = File::create(format!("Localized_{}.ts", self.lang))?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(self.document().as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsTs {
+    fn document(&self) -> String {
+        let translations: BTreeMap<&str, &TranslationOut> = self.translations.iter()
+            .map(|t| (t.key.as_str(), t))
+            .collect();
+
+        let mut contexts: BTreeMap<&str, Vec<&TranslationOut>> = BTreeMap::new();
+        for source in &self.sources {
+            contexts.entry(key_context(&source.key)).or_default().push(source);
+        }
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!DOCTYPE TS>\n");
+        out.push_str(&format!("<TS version=\"2.1\" language=\"{}\" sourcelanguage=\"{}\">\n",
+                              po_lang(&self.lang), po_lang(&self.source_lang)));
+
+        for (context, sources) in contexts {
+            out.push_str(&format!("<context>\n    <name>{}</name>\n", escape(context)));
+
+            for source in sources {
+                out.push_str(&self.message(source, translations.get(source.key.as_str()).copied()));
+            }
+
+            out.push_str("</context>\n");
+        }
+
+        out.push_str("</TS>\n");
+        out
+    }
+
+    fn message(&self, source: &TranslationOut, translation: Option<&TranslationOut>) -> String {
+        let translation = translation.filter(|t| !t.missing);
+        let numerus = !source.plurals.is_empty() || translation.is_some_and(|t| !t.plurals.is_empty());
+        let mut out = String::new();
+
+        if numerus {
+            out.push_str(&format!("    <message id=\"{}\" numerus=\"yes\">\n", escape(&source.key)));
+        } else {
+            out.push_str(&format!("    <message id=\"{}\">\n", escape(&source.key)));
+        }
+
+        out.push_str(&format!("        <source>{}</source>\n", escape(&qt_format(&source.value, numerus))));
+        if let Some(comment) = &source.comment {
+            out.push_str(&format!("        <extracomment>{}</extracomment>\n", escape(comment)));
+        }
+
+        let unfinished = translation.map_or(true, |t| t.outdated);
+        out.push_str(if unfinished { "        <translation type=\"unfinished\">" } else { "        <translation>" });
+
+        match translation {
+            Some(translation) if numerus => {
+                out.push('\n');
+                for category in plural_forms(&self.lang).categories {
+                    let value = translation.plurals.get(*category).unwrap_or(&translation.value);
+                    out.push_str(&format!("            <numerusform>{}</numerusform>\n", escape(&qt_format(value, true))));
+                }
+                out.push_str("        ");
+            }
+            Some(translation) => out.push_str(&escape(&qt_format(&translation.value, false))),
+            None => {}
+        }
+
+        out.push_str("</translation>\n    </message>\n");
+        out
+    }
+}
+
+/// Context of the key, the part before the last dot.
+fn key_context(key: &str) -> &str {
+    key.rfind('.')
+        .map(|idx| &key[..idx])
+        .unwrap_or("")
+}
+
+pub(crate) fn qt_format(value: &str, numerus: bool) -> String {
+    let count_index = if numerus { count_index(value) } else { None };
+
+    replace_placeholders(value, |p| {
+        if count_index == Some(p.index) { "%n".to_string() } else { format!("%{}", p.index) }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_generator::TranslationOut;
+    use crate::ts_generator::TranslationsTs;
+
+    #[test]
+    fn generate_ts() {
+        let mut files_source = TranslationOut::new("app.list.files", "%d files in %s");
+        files_source.plurals.insert("one".to_string(), "%d file in %s".to_string());
+        let mut files = TranslationOut::new("app.list.files", "%d plików w %s");
+        files.plurals.insert("one".to_string(), "%d plik w %s".to_string());
+        files.plurals.insert("few".to_string(), "%d pliki w %s".to_string());
+        let mut hello_source = TranslationOut::new("app.hello", "Hello <%s>");
+        hello_source.comment = Some("Greeting".to_string());
+        let mut hello = TranslationOut::new("app.hello", "Witaj <%s>");
+        hello.outdated = true;
+        let mut missing = TranslationOut::new("title", "title");
+        missing.missing = true;
+
+        let generator = TranslationsTs {
+            lang: "pl-PL".to_string(),
+            source_lang: "en-US".to_string(),
+            sources: vec![hello_source, files_source, TranslationOut::new("title", "Title")],
+            translations: vec![hello, files, missing]
+        };
+        let document = generator.document();
+
+        assert!(document.contains("<TS version=\"2.1\" language=\"pl_PL\" sourcelanguage=\"en_US\">"));
+        assert!(document.contains("<context>\n    <name>app</name>\n    <message id=\"app.hello\">\n        \
+                                   <source>Hello &lt;%1&gt;</source>\n        \
+                                   <extracomment>Greeting</extracomment>\n        \
+                                   <translation type=\"unfinished\">Witaj &lt;%1&gt;</translation>\n"));
+        assert!(document.contains("<name>app.list</name>\n    <message id=\"app.list.files\" numerus=\"yes\">\n        \
+                                   <source>%n files in %2</source>\n        <translation>\n            \
+                                   <numerusform>%n plik w %2</numerusform>\n            \
+                                   <numerusform>%n pliki w %2</numerusform>\n            \
+                                   <numerusform>%n plików w %2</numerusform>\n        </translation>\n"));
+        assert!(document.contains("<name></name>\n    <message id=\"title\">\n        <source>Title</source>\n        \
+                                   <translation type=\"unfinished\"></translation>\n"));
+    }
+}
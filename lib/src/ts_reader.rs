@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::excel_reader::{ImportResult, Importer};
+use crate::json_data::{DataRootTranslations, Project};
+use crate::placeholders::{count_index, parse_placeholders, PlaceholderType};
+use crate::po_generator::{plural_forms, po_lang};
+use crate::xml::attribute;
+
+/// Translation types meaning the message is not translated or not used anymore.
+const SKIPPED_TYPES: [&str; 3] = ["unfinished", "obsolete", "vanished"];
+
+#[derive(Debug)]
+pub enum TsError {
+    Xml(quick_xml::Error),
+    MissingLanguage,
+    InvalidLanguage(String)
+}
+
+impl fmt::Display for TsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TsError::Xml(e) => write!(f, "invalid ts file: {}", e),
+            TsError::MissingLanguage => write!(f, "no language in ts file"),
+            TsError::InvalidLanguage(lang) => write!(f, "language \"{}\" not in project", lang)
+        }
+    }
+}
+
+impl Error for TsError {}
+
+impl From<quick_xml::Error> for TsError {
+    fn from(e: quick_xml::Error) -> Self {
+        TsError::Xml(e)
+    }
+}
+
+type Result<T> = std::result::Result<T, TsError>;
+
+#[derive(Default)]
+struct Message {
+    id: String,
+    numerus: bool,
+    skipped: bool,
+    translation: String,
+    forms: Vec<String>
+}
+
+/// Imports Qt Linguist translations into project. Messages are matched to keys by `id`, only
+/// finished translations are imported and marked as up to date with the source value.
+pub fn import_ts(data: &str, data_root: &mut DataRootTranslations, project: &Project, project_chain: &[u16],
+                 ignore_unknown: bool) -> Result<ImportResult> {
+    let mut reader = Reader::from_str(data);
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
+
+    let mut lang: Option<String> = None;
+    let mut message: Option<Message> = None;
+    let mut in_translation = false;
+    let mut in_form = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                match e.local_name().as_ref() {
+                    b"TS" => lang = attribute(&e, "language")?,
+                    b"message" => {
+                        lang = Some(project_lang(&lang, project)?);
+                        message = Some(Message {
+                            id: attribute(&e, "id")?.unwrap_or_default(),
+                            numerus: attribute(&e, "numerus")?.as_deref() == Some("yes"),
+                            ..Message::default()
+                        });
+                    }
+                    b"translation" => {
+                        if let Some(message) = message.as_mut() {
+                            message.skipped = attribute(&e, "type")?
+                                .is_some_and(|t| SKIPPED_TYPES.contains(&t.as_str()));
+                            in_translation = true;
+                        }
+                    }
+                    b"numerusform" if in_translation => {
+                        if let Some(message) = message.as_mut() {
+                            message.forms.push(String::new());
+                            in_form = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(text) if in_translation => {
+                if let Some(message) = message.as_mut() {
+                    let text = text.unescape()?;
+
+                    if in_form {
+                        message.forms.last_mut().into_iter().for_each(|form| form.push_str(&text));
+                    } else if !message.numerus {
+                        message.translation.push_str(&text);
+                    }
+                }
+            }
+            Event::End(e) => {
+                match e.local_name().as_ref() {
+                    b"numerusform" => in_form = false,
+                    b"translation" => in_translation = false,
+                    b"message" => {
+                        if let (Some(message), Some(lang)) = (message.take(), &lang) {
+                            import_message(&mut importer, message, lang);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(importer.result)
+}
+
+fn import_message(importer: &mut Importer, message: Message, lang: &str) {
+    if message.skipped || message.id.is_empty() {
+        return;
+    }
+
+    let source = importer.data_root.get(&message.id)
+        .and_then(|t| t.resolve_in(importer.project_chain, &importer.project.default_lang))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut imported = false;
+    if message.numerus {
+        let categories = plural_forms(lang).categories;
+
+        for (category, form) in categories.iter().zip(message.forms) {
+            if !form.is_empty() {
+                importer.plural(&message.id, lang, category, printf_format(&form, &source));
+                imported = true;
+            }
+        }
+    } else if !message.translation.is_empty() {
+        importer.value(&message.id, lang, printf_format(&message.translation, &source));
+        imported = true;
+    }
+
+    if imported {
+        importer.confirm(&message.id, lang);
+    }
+}
+
+/// Converts Qt placeholders back to printf ones, with types taken from the source value. They are
+/// positional only when the arguments are not in order.
+fn printf_format(value: &str, source: &str) -> String {
+    let kinds: BTreeMap<usize, PlaceholderType> = parse_placeholders(source).into_iter()
+        .map(|p| (p.index, p.kind))
+        .collect();
+    let count = count_index(source).unwrap_or(1);
+
+    let mut parts: Vec<(String, Option<usize>)> = vec![(String::new(), None)];
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let index = match chars.peek() {
+            Some('n') if c == '%' => {
+                chars.next();
+                Some(count)
+            }
+            Some(d) if c == '%' && d.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    digits.push(*d);
+                    chars.next();
+                }
+                digits.parse().ok()
+            }
+            _ => None
+        };
+
+        match index {
+            Some(index) => parts.push((String::new(), Some(index))),
+            None => parts.last_mut().into_iter().for_each(|(text, _)| {
+                // Percent sign is literal in Qt, printf needs it escaped.
+                if c == '%' { text.push_str("%%") } else { text.push(c) }
+            })
+        }
+    }
+
+    let indexes: Vec<usize> = parts.iter().filter_map(|(_, index)| *index).collect();
+    let sequential = indexes.iter().enumerate().all(|(idx, index)| idx + 1 == *index);
+
+    parts.into_iter()
+        .map(|(text, index)| match index {
+            Some(index) => {
+                let conversion = match kinds.get(&index) {
+                    Some(PlaceholderType::Int) => 'd',
+                    Some(PlaceholderType::Double) => 'f',
+                    _ if index == count && value.contains("%n") => 'd',
+                    _ => 's'
+                };
+
+                if sequential { format!("%{}{}", conversion, text) } else { format!("%{}${}{}", index, conversion, text) }
+            }
+            None => text
+        })
+        .collect()
+}
+
+/// Project language of the file, `pl_PL` in file matches `pl-PL` in project.
+fn project_lang(lang: &Option<String>, project: &Project) -> Result<String> {
+    let lang = lang.as_ref().ok_or(TsError::MissingLanguage)?;
+
+    project.langs.iter()
+        .find(|l| po_lang(l).eq(&po_lang(lang)))
+        .cloned()
+        .ok_or_else(|| TsError::InvalidLanguage(lang.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ts_generator::qt_format;
+    use crate::ts_reader::printf_format;
+
+    #[test]
+    fn convert_qt_placeholders() {
+        assert_eq!("%d plików w %s", printf_format("%n plików w %2", "%d files in %s"));
+        assert_eq!("w %2$s: %1$d plików", printf_format("w %2: %n plików", "%d files in %s"));
+        assert_eq!("Witaj %s, %d%%", printf_format("Witaj %1, %2%", "Hello %s, %d%%"));
+        assert_eq!("%s", printf_format("%1", ""));
+    }
+
+    #[test]
+    fn convert_percent_signs_round_trip() {
+        let qt = qt_format("Gotowe %d%%, 100%% %s", false);
+
+        assert_eq!("Gotowe %1%, 100% %2", qt);
+        assert_eq!("Gotowe %d%%, 100%% %s", printf_format(&qt, "Done %d%%, 100%% %s"));
+    }
+}
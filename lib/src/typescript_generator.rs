@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use crate::placeholders::{argument_name, count_index, parse_placeholders, PlaceholderType, COUNT_ARGUMENT};
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// TypeScript declarations for the keys of the web exports, written to `messages.d.ts`.
+/// `MessageKey` is the union of all keys and `MessageParams` maps every key to the parameters its
+/// default language value requires, named like the json placeholders, e.g. `{{arg1}}`.
+pub struct TranslationsTypeScript {
+    /// Default language strings.
+    pub sources: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsTypeScript {
+    fn generate(&self) -> std::io::Result<()> {
+        let file = File::create("messages.d.ts")?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(self.declarations().as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsTypeScript {
+    fn declarations(&self) -> String {
+        let mut out = String::from("// Generated by stringc, do not edit.\n\n");
+
+        if self.sources.is_empty() {
+            out.push_str("export type MessageKey = never;\n\nexport interface MessageParams {}\n");
+            return out;
+        }
+
+        out.push_str("export type MessageKey =\n");
+        let keys: Vec<String> = self.sources.iter()
+            .map(|source| format!("    | {}", ts_string(&source.key)))
+            .collect();
+        out.push_str(&keys.join("\n"));
+        out.push_str(";\n\nexport interface MessageParams {\n");
+
+        for source in &self.sources {
+            let comment = source.value.lines().collect::<Vec<&str>>().join(" ").replace("*/", "*\\/");
+            out.push_str(format!("    /** {}", comment).trim_end());
+            out.push_str(" */\n");
+            out.push_str(&format!("    {}: {};\n", ts_string(&source.key), parameters(source)));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Object type of the parameters, plural keys always take the `count`.
+fn parameters(source: &TranslationOut) -> String {
+    let plural = !source.plurals.is_empty();
+    let count_index = if plural { count_index(&source.value) } else { None };
+
+    let mut parameters: BTreeMap<usize, String> = parse_placeholders(&source.value).iter()
+        .map(|p| (p.index, format!("{}: {}", argument_name(p, count_index), ts_type(p.kind))))
+        .collect();
+    if plural && count_index.is_none() {
+        parameters.insert(0, format!("{}: number", COUNT_ARGUMENT));
+    }
+
+    if parameters.is_empty() {
+        "Record<string, never>".to_string()
+    } else {
+        format!("{{ {} }}", parameters.values().cloned().collect::<Vec<String>>().join("; "))
+    }
+}
+
+fn ts_type(kind: PlaceholderType) -> &'static str {
+    match kind {
+        PlaceholderType::Text => "string",
+        PlaceholderType::Int | PlaceholderType::Double => "number"
+    }
+}
+
+fn ts_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_generator::TranslationOut;
+    use crate::typescript_generator::TranslationsTypeScript;
+
+    #[test]
+    fn generate_declarations() {
+        let mut files = TranslationOut::new("app.files", "%d files in %s");
+        files.plurals.insert("one".to_string(), "%d file in %s".to_string());
+        let mut items = TranslationOut::new("app.items", "Items");
+        items.plurals.insert("one".to_string(), "Item".to_string());
+
+        let generator = TranslationsTypeScript {
+            sources: vec![
+                files,
+                TranslationOut::new("app.hello", "Hello %s, %.1f%%"),
+                items,
+                TranslationOut::new("title", "Title")
+            ]
+        };
+
+        assert_eq!("// Generated by stringc, do not edit.\n\n\
+                    export type MessageKey =\n    \
+                        | \"app.files\"\n    \
+                        | \"app.hello\"\n    \
+                        | \"app.items\"\n    \
+                        | \"title\";\n\n\
+                    export interface MessageParams {\n    \
+                        /** %d files in %s */\n    \
+                        \"app.files\": { count: number; arg2: string };\n    \
+                        /** Hello %s, %.1f%% */\n    \
+                        \"app.hello\": { arg1: string; arg2: number };\n    \
+                        /** Items */\n    \
+                        \"app.items\": { count: number };\n    \
+                        /** Title */\n    \
+                        \"title\": Record<string, never>;\n\
+                    }\n", generator.declarations());
+    }
+
+    #[test]
+    fn generate_empty_declarations() {
+        let generator = TranslationsTypeScript { sources: vec![] };
+
+        assert!(generator.declarations().contains("export type MessageKey = never;"));
+    }
+}
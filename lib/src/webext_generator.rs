@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{Error, ErrorKind, LineWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::placeholders::{parse_placeholders, replace_placeholders_escaped};
+use crate::strings_generator::{unique_names, Generator, TranslationOut};
+
+/// Regional locales supported by browsers, for the other languages only the language part is
+/// used, e.g. `pl` for `pl-PL`.
+const REGIONAL_LOCALES: [&str; 8] = ["en_AU", "en_GB", "en_US", "es_419", "pt_BR", "pt_PT", "zh_CN", "zh_TW"];
+
+/// WebExtension messages of a language, written to `_locales/<locale>/messages.json`. Placeholders
+/// are named `arg1`, `arg2`, ... Plural keys are written with their `other` form only.
+pub struct TranslationsWebExt {
+    pub lang: String,
+    pub translations: Vec<TranslationOut>
+}
+
+#[derive(Serialize)]
+struct Message {
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    placeholders: BTreeMap<String, MessagePlaceholder>
+}
+
+#[derive(Serialize)]
+struct MessagePlaceholder {
+    content: String
+}
+
+impl Generator for TranslationsWebExt {
+    fn generate(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.messages()?)?;
+
+        let dir = Path::new("_locales").join(webext_locale(&self.lang));
+        create_dir_all(&dir)?;
+
+        let file = File::create(dir.join("messages.json"))?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(json.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsWebExt {
+    /// Messages by name. Names are case insensitive, so keys which differ only by case or by
+    /// characters not allowed in names are an error.
+    fn messages(&self) -> std::io::Result<BTreeMap<String, Message>> {
+        let mut messages = BTreeMap::new();
+        let translations = self.translations.iter().filter(|t| !t.missing);
+
+        for (_, translation) in unique_names(translations, "messages.json", |key| message_name(key).to_lowercase())? {
+
+            let placeholders = parse_placeholders(&translation.value).iter()
+                .map(|p| (format!("arg{}", p.index), MessagePlaceholder { content: format!("${}", p.index) }))
+                .collect();
+
+            let message = Message {
+                message: replace_placeholders_escaped(&translation.value, |text| text.replace('$', "$$"),
+                                                      |p| format!("$ARG{}$", p.index)),
+                description: translation.comment.clone(),
+                placeholders
+            };
+            messages.insert(message_name(&translation.key), message);
+        }
+
+        Ok(messages)
+    }
+}
+
+/// Message names can contain only ASCII letters, digits, `_` and `@`, other characters are
+/// replaced with `_`.
+fn message_name(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '@' { c } else { '_' })
+        .collect()
+}
+
+/// Checks that project languages map to distinct locales, e.g. `fr-FR` and `fr-CA` would both
+/// be written to `_locales/fr`.
+pub(crate) fn check_locales(langs: &[&str]) -> std::io::Result<()> {
+    let mut locales: BTreeMap<String, &str> = BTreeMap::new();
+
+    for lang in langs {
+        let locale = webext_locale(lang);
+
+        if let Some(other_lang) = locales.insert(locale.to_string(), lang) {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("languages \"{}\" and \"{}\" are both written to locale \"{}\"",
+                                          other_lang, lang, locale)));
+        }
+    }
+
+    Ok(())
+}
+
+fn webext_locale(lang: &str) -> String {
+    let locale = lang.replace('-', "_");
+
+    if REGIONAL_LOCALES.contains(&locale.as_str()) {
+        locale
+    } else {
+        locale.split('_').next().unwrap_or(&locale).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_generator::TranslationOut;
+    use crate::webext_generator::{check_locales, webext_locale, TranslationsWebExt};
+
+    #[test]
+    fn map_locales() {
+        assert_eq!("pt_BR", webext_locale("pt-BR"));
+        assert_eq!("pl", webext_locale("pl-PL"));
+        assert_eq!("de", webext_locale("de"));
+    }
+
+    #[test]
+    fn check_clashing_locales() {
+        assert!(check_locales(&["en-US", "en-GB", "pl-PL"]).is_ok());
+        assert!(check_locales(&["en-US", "fr-FR", "fr-CA"]).is_err());
+    }
+
+    #[test]
+    fn generate_messages() {
+        let mut hello = TranslationOut::new("app.hello", "Hello %s, you owe $%2$.2f");
+        hello.comment = Some("Greeting".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsWebExt { lang: "en-US".to_string(), translations: vec![hello, missing] };
+        let json = serde_json::to_value(generator.messages().unwrap()).unwrap();
+
+        assert_eq!("Hello $ARG1$, you owe $$$ARG2$", json["app_hello"]["message"]);
+        assert_eq!("Greeting", json["app_hello"]["description"]);
+        assert_eq!("$2", json["app_hello"]["placeholders"]["arg2"]["content"]);
+        assert!(json["app_missing"].is_null());
+    }
+}
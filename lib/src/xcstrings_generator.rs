@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::placeholders::{replace_placeholders_escaped, PlaceholderType};
+use crate::strings_generator::{Generator, TranslationOut};
+
+const STATE_TRANSLATED: &str = "translated";
+const STATE_NEEDS_REVIEW: &str = "needs_review";
+
+/// Xcode String Catalog, all languages of a project in one file.
+pub struct TranslationsXcStrings {
+    pub source_lang: String,
+    pub strings: Vec<(String, Vec<TranslationOut>)>
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StringCatalog {
+    pub source_language: String,
+    pub strings: BTreeMap<String, CatalogString>,
+    pub version: String
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CatalogString {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extraction_state: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub localizations: BTreeMap<String, Localization>
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Localization {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub string_unit: Option<StringUnit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variations: Option<Variations>
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct Variations {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub plural: BTreeMap<String, Variation>
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Variation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub string_unit: Option<StringUnit>
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct StringUnit {
+    pub state: String,
+    pub value: String
+}
+
+impl Generator for TranslationsXcStrings {
+    fn generate(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.catalog())?;
+
+        let file = File::create("Localizable.xcstrings")?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(json.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsXcStrings {
+    fn catalog(&self) -> StringCatalog {
+        let mut strings: BTreeMap<String, CatalogString> = BTreeMap::new();
+
+        for (lang, translations) in &self.strings {
+            for translation in translations.iter().filter(|t| !t.missing) {
+                let catalog_string = strings.entry(translation.key.to_string())
+                    .or_insert_with(|| CatalogString {
+                        comment: translation.comment.clone(),
+                        extraction_state: Some("manual".to_string()),
+                        localizations: BTreeMap::new()
+                    });
+
+                catalog_string.localizations.insert(lang.to_string(), TranslationsXcStrings::localization(translation));
+            }
+        }
+
+        StringCatalog { source_language: self.source_lang.to_string(), strings, version: "1.0".to_string() }
+    }
+
+    fn localization(translation: &TranslationOut) -> Localization {
+        let state = if translation.outdated { STATE_NEEDS_REVIEW } else { STATE_TRANSLATED };
+        let string_unit = |value: &str| StringUnit {
+            state: state.to_string(),
+            value: TranslationsXcStrings::format_placeholders(value)
+        };
+
+        if translation.plurals.is_empty() {
+            return Localization { string_unit: Some(string_unit(&translation.value)), variations: None };
+        }
+
+        let plural = translation.plurals.iter()
+            .map(|(category, value)| (category.to_string(), Variation { string_unit: Some(string_unit(value)) }))
+            .collect();
+
+        Localization { string_unit: None, variations: Some(Variations { plural }) }
+    }
+
+    /// Catalog plurals need integer arguments, so numbers keep their type here.
+    pub(crate) fn format_placeholders(translation: &str) -> String {
+        replace_placeholders_escaped(translation, |text| text.replace('%', "%%"), |placeholder| {
+            let (spec, conversion) = split_conversion(&translation[placeholder.range.clone()]);
+
+            match (placeholder.kind, conversion) {
+                (PlaceholderType::Text, "s") => format!("{}@", spec),
+                (PlaceholderType::Int, _) => format!("{}ll{}", spec, conversion),
+                _ => format!("{}{}", spec, conversion)
+            }
+        })
+    }
+
+    pub(crate) fn parse_placeholders(translation: &str) -> String {
+        replace_placeholders_escaped(translation, |text| text.replace('%', "%%"), |placeholder| {
+            let (spec, conversion) = split_conversion(&translation[placeholder.range.clone()]);
+
+            match (placeholder.kind, conversion) {
+                (PlaceholderType::Text, "@") => format!("{}s", spec),
+                _ => format!("{}{}", spec, conversion)
+            }
+        })
+    }
+}
+
+/// Splits placeholder to its part before the length modifier and the conversion character,
+/// e.g. `%1$lld` to `%1$` and `d`.
+fn split_conversion(placeholder: &str) -> (&str, &str) {
+    let (spec, conversion) = placeholder.split_at(placeholder.len() - 1);
+    (spec.trim_end_matches(|c| "hlqzjt".contains(c)), conversion)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_generator::TranslationOut;
+    use crate::xcstrings_generator::TranslationsXcStrings;
+
+    fn translation(key: &str, value: &str, outdated: bool) -> TranslationOut {
+        TranslationOut { outdated, ..TranslationOut::new(key, value) }
+    }
+
+    #[test]
+    fn generate_catalog() {
+        let mut files = translation("app.files", "%d files", false);
+        files.comment = Some("Files count".to_string());
+        files.plurals.insert("one".to_string(), "%d file".to_string());
+        files.plurals.insert("other".to_string(), "%d files".to_string());
+
+        let mut missing = translation("app.files", "app.files", false);
+        missing.missing = true;
+
+        let generator = TranslationsXcStrings {
+            source_lang: "en-US".to_string(),
+            strings: vec![
+                ("en-US".to_string(), vec![files, translation("app.hello", "Hello %s", false)]),
+                ("pl-PL".to_string(), vec![translation("app.hello", "Witaj %s", true), missing])
+            ]
+        };
+
+        let json = serde_json::to_value(generator.catalog()).unwrap();
+
+        assert_eq!("en-US", json["sourceLanguage"]);
+        assert_eq!("Files count", json["strings"]["app.files"]["comment"]);
+        assert_eq!("%lld file", json["strings"]["app.files"]["localizations"]["en-US"]["variations"]["plural"]["one"]["stringUnit"]["value"]);
+        assert_eq!("Hello %@", json["strings"]["app.hello"]["localizations"]["en-US"]["stringUnit"]["value"]);
+        assert_eq!("translated", json["strings"]["app.hello"]["localizations"]["en-US"]["stringUnit"]["state"]);
+        assert_eq!("needs_review", json["strings"]["app.hello"]["localizations"]["pl-PL"]["stringUnit"]["state"]);
+        assert!(json["strings"]["app.files"]["localizations"]["pl-PL"].is_null());
+    }
+
+    #[test]
+    fn convert_placeholders() {
+        let value = "%s: %2$ld of %1$s, 5$s for 100%% (%.1f%%)";
+        let formatted = TranslationsXcStrings::format_placeholders(value);
+
+        assert_eq!("%@: %2$lld of %1$@, 5$s for 100%% (%.1f%%)", formatted);
+        assert_eq!("%s: %2$d of %1$s, 5$s for 100%% (%.1f%%)", TranslationsXcStrings::parse_placeholders(&formatted));
+    }
+}
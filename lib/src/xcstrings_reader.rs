@@ -0,0 +1,58 @@
+use crate::excel_reader::{ImportResult, Importer};
+use crate::json_data::{DataRootTranslations, Project};
+use crate::xcstrings_generator::{StringCatalog, TranslationsXcStrings};
+
+/// Imports Xcode String Catalog contents into project. Only project languages are imported,
+/// comments are imported for all keys which are not ignored.
+pub fn import_xcstrings(data: &str, data_root: &mut DataRootTranslations, project: &Project,
+                        project_chain: &[u16], ignore_unknown: bool) -> serde_json::Result<ImportResult> {
+    let catalog: StringCatalog = serde_json::from_str(data)?;
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
+    let langs = importer.langs();
+
+    for (key, catalog_string) in catalog.strings {
+        if let Some(comment) = catalog_string.comment {
+            if let Some(translation) = importer.translation(&key) {
+                translation.comment = Some(comment);
+            }
+        }
+
+        for lang in &langs {
+            let localization = match catalog_string.localizations.get(lang) {
+                Some(localization) => localization,
+                None => continue
+            };
+
+            if let Some(unit) = &localization.string_unit {
+                let stored = importer.data_root.get(&key)
+                    .and_then(|t| t.resolve_in(importer.project_chain, lang));
+                let value = stored_form(&unit.value, stored);
+                importer.value(&key, lang, value);
+            }
+
+            let plural = localization.variations.iter()
+                .flat_map(|v| v.plural.iter());
+
+            for (category, variation) in plural {
+                if let Some(unit) = &variation.string_unit {
+                    let stored = importer.data_root.get(&key)
+                        .and_then(|t| t.plurals_in(importer.project_chain, lang))
+                        .and_then(|plurals| plurals.get(category));
+                    let value = stored_form(&unit.value, stored);
+                    importer.plural(&key, lang, category, value);
+                }
+            }
+        }
+    }
+
+    Ok(importer.result)
+}
+
+/// Catalog value in the stored form. Stored value is kept when it is exported as the same catalog
+/// value, e.g. `50% off` is not changed to `50%% off`.
+fn stored_form(value: &str, stored: Option<&String>) -> String {
+    match stored {
+        Some(stored) if TranslationsXcStrings::format_placeholders(stored) == value => stored.to_string(),
+        _ => TranslationsXcStrings::parse_placeholders(value)
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use quick_xml::escape::escape;
+
+use crate::po_generator::plural_forms;
+use crate::strings_generator::{Generator, TranslationOut};
+
+#[derive(Clone)]
+pub enum XliffVersion {
+    V12,
+    V20
+}
+
+/// XLIFF file for translation vendors, one target language of a project. Plural forms are
+/// exported as separate units with `key[category]` ids, for categories of the target language.
+pub struct TranslationsXliff {
+    pub version: XliffVersion,
+    pub project_name: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    /// Default language translations by key.
+    pub sources: BTreeMap<String, TranslationOut>,
+    pub translations: Vec<TranslationOut>
+}
+
+/// Translation unit written to file, target is `None` when there is no translation yet.
+struct Unit<'a> {
+    id: String,
+    source: &'a str,
+    target: Option<&'a str>,
+    outdated: bool,
+    comment: Option<&'a str>
+}
+
+impl Generator for TranslationsXliff {
+    fn generate(&self) -> std::io::Result<()> {
+        let file_name = match self.version {
+            XliffVersion::V12 => format!("Localized_{}.xlf", self.target_lang),
+            XliffVersion::V20 => format!("Localized_{}.xliff", self.target_lang)
+        };
+
+        let file = File::create(file_name)?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(self.document().as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsXliff {
+    fn document(&self) -> String {
+        match self.version {
+            XliffVersion::V12 => self.document_v12(),
+            XliffVersion::V20 => self.document_v20()
+        }
+    }
+
+    fn document_v12(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n");
+        out.push_str(&format!("  <file original=\"{}\" source-language=\"{}\" target-language=\"{}\" datatype=\"plaintext\">\n",
+                              escape(&self.project_name), self.source_lang, self.target_lang));
+        out.push_str("    <body>\n");
+
+        for unit in self.units() {
+            let state = match unit.target {
+                None => "new",
+                Some(_) if unit.outdated => "needs-review-translation",
+                Some(_) => "translated"
+            };
+
+            out.push_str(&format!("      <trans-unit id=\"{}\">\n", escape(&unit.id)));
+            out.push_str(&format!("        <source>{}</source>\n", escape(unit.source)));
+            out.push_str(&format!("        <target state=\"{}\">{}</target>\n",
+                                  state, escape(unit.target.unwrap_or_default())));
+            if let Some(comment) = unit.comment {
+                out.push_str(&format!("        <note>{}</note>\n", escape(comment)));
+            }
+            out.push_str("      </trans-unit>\n");
+        }
+
+        out.push_str("    </body>\n");
+        out.push_str("  </file>\n");
+        out.push_str("</xliff>\n");
+        out
+    }
+
+    fn document_v20(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!("<xliff version=\"2.0\" xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" srcLang=\"{}\" trgLang=\"{}\">\n",
+                              self.source_lang, self.target_lang));
+        out.push_str(&format!("  <file id=\"f1\" original=\"{}\">\n", escape(&self.project_name)));
+
+        for unit in self.units() {
+            out.push_str(&format!("    <unit id=\"{}\">\n", escape(&unit.id)));
+            if let Some(comment) = unit.comment {
+                out.push_str(&format!("      <notes>\n        <note>{}</note>\n      </notes>\n", escape(comment)));
+            }
+
+            match unit.target {
+                None => out.push_str("      <segment state=\"initial\">\n"),
+                Some(_) if unit.outdated => {
+                    out.push_str("      <segment state=\"initial\" subState=\"stringc:outdated\">\n")
+                }
+                Some(_) => out.push_str("      <segment state=\"translated\">\n")
+            }
+
+            out.push_str(&format!("        <source>{}</source>\n", escape(unit.source)));
+            if let Some(target) = unit.target {
+                out.push_str(&format!("        <target>{}</target>\n", escape(target)));
+            }
+            out.push_str("      </segment>\n");
+            out.push_str("    </unit>\n");
+        }
+
+        out.push_str("  </file>\n");
+        out.push_str("</xliff>\n");
+        out
+    }
+
+    /// Units of all translations, keys with plurals get one more unit for every plural category of
+    /// the target language.
+    fn units(&self) -> Vec<Unit<'_>> {
+        let categories = plural_forms(&self.target_lang).categories;
+        let mut units = vec![];
+
+        for translation in &self.translations {
+            let source = self.sources.get(&translation.key);
+            let source_value = source
+                .map(|s| s.value.as_str())
+                .unwrap_or_else(|| translation.key.as_str());
+
+            units.push(Unit {
+                id: translation.key.to_string(),
+                source: source_value,
+                target: if translation.missing { None } else { Some(translation.value.as_str()) },
+                outdated: translation.outdated,
+                comment: translation.comment.as_deref()
+            });
+
+            let source_plurals = source.map(|s| &s.plurals).filter(|p| !p.is_empty());
+            if source_plurals.is_none() && translation.plurals.is_empty() {
+                continue;
+            }
+
+            for category in categories {
+                let source_plural = source_plurals
+                    .and_then(|p| p.get(*category).or_else(|| p.get("other")))
+                    .map(|v| v.as_str())
+                    .unwrap_or(source_value);
+
+                units.push(Unit {
+                    id: format!("{}[{}]", translation.key, category),
+                    source: source_plural,
+                    target: translation.plurals.get(*category).filter(|_| !translation.missing).map(|v| v.as_str()),
+                    outdated: translation.outdated,
+                    comment: translation.comment.as_deref()
+                });
+            }
+        }
+
+        units
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::strings_generator::TranslationOut;
+    use crate::xliff_generator::{TranslationsXliff, XliffVersion};
+
+    fn generator(version: XliffVersion) -> TranslationsXliff {
+        let mut files = TranslationOut::new("app.files", "%d files");
+        files.plurals.insert("one".to_string(), "%d file".to_string());
+        files.plurals.insert("other".to_string(), "%d files".to_string());
+
+        let mut sources = BTreeMap::new();
+        sources.insert("app.hello".to_string(), TranslationOut::new("app.hello", "Hello <b>%s</b>"));
+        sources.insert("app.new".to_string(), TranslationOut::new("app.new", "New"));
+        sources.insert("app.files".to_string(), files);
+
+        let mut files = TranslationOut::new("app.files", "%d pliki");
+        files.plurals.insert("one".to_string(), "%d plik".to_string());
+        files.plurals.insert("few".to_string(), "%d pliki".to_string());
+
+        TranslationsXliff {
+            version,
+            project_name: "Test & Co".to_string(),
+            source_lang: "en-US".to_string(),
+            target_lang: "pl-PL".to_string(),
+            sources,
+            translations: vec![
+                TranslationOut {
+                    key: "app.hello".to_string(),
+                    value: "Witaj <b>%s</b>".to_string(),
+                    comment: Some("Greeting".to_string()),
+                    plurals: BTreeMap::new(),
+                    outdated: true,
+                    missing: false
+                },
+                TranslationOut {
+                    key: "app.new".to_string(),
+                    value: "app.new".to_string(),
+                    comment: None,
+                    plurals: BTreeMap::new(),
+                    outdated: false,
+                    missing: true
+                },
+                files
+            ]
+        }
+    }
+
+    #[test]
+    fn generate_xliff_v12() {
+        let document = generator(XliffVersion::V12).document();
+
+        assert!(document.contains("<file original=\"Test &amp; Co\" source-language=\"en-US\" target-language=\"pl-PL\""));
+        assert!(document.contains("<source>Hello &lt;b&gt;%s&lt;/b&gt;</source>"));
+        assert!(document.contains("<target state=\"needs-review-translation\">Witaj &lt;b&gt;%s&lt;/b&gt;</target>"));
+        assert!(document.contains("<note>Greeting</note>"));
+        assert!(document.contains("<target state=\"new\"></target>"));
+    }
+
+    #[test]
+    fn generate_xliff_plurals() {
+        let document = generator(XliffVersion::V12).document();
+
+        assert!(document.contains("<trans-unit id=\"app.files[one]\">\n        <source>%d file</source>\n        \
+                                   <target state=\"translated\">%d plik</target>"));
+        assert!(document.contains("<trans-unit id=\"app.files[many]\">\n        <source>%d files</source>\n        \
+                                   <target state=\"new\"></target>"));
+        assert!(!document.contains("app.files[other]"));
+
+        let document = generator(XliffVersion::V20).document();
+
+        assert!(document.contains("<unit id=\"app.files[few]\">\n      <segment state=\"translated\">\n        \
+                                   <source>%d files</source>\n        <target>%d pliki</target>"));
+    }
+
+    #[test]
+    fn generate_xliff_v20() {
+        let document = generator(XliffVersion::V20).document();
+
+        assert!(document.contains("srcLang=\"en-US\" trgLang=\"pl-PL\""));
+        assert!(document.contains("<segment state=\"initial\" subState=\"stringc:outdated\">"));
+        assert!(document.contains("<target>Witaj &lt;b&gt;%s&lt;/b&gt;</target>"));
+        assert!(!document.contains("<target>app.new</target>"));
+    }
+}
@@ -0,0 +1,198 @@
+use std::error::Error;
+use std::fmt;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::excel_reader::{ImportResult, Importer};
+use crate::json_data::{DataRootTranslations, Project, PLURAL_CATEGORIES};
+use crate::xml::attribute;
+
+/// Target states meaning the translation was checked by translator.
+const CONFIRMED_STATES: [&str; 4] = ["translated", "reviewed", "signed-off", "final"];
+/// Target states meaning there is no translation yet.
+const EMPTY_STATES: [&str; 3] = ["new", "needs-translation", "initial"];
+/// Empty inline elements which only mark a position in target and can be dropped.
+const MARKERS: [&str; 2] = ["sm", "em"];
+
+#[derive(Debug)]
+pub enum XliffError {
+    Xml(quick_xml::Error),
+    MissingLanguage,
+    InvalidLanguage(String)
+}
+
+impl fmt::Display for XliffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XliffError::Xml(e) => write!(f, "invalid xliff file: {}", e),
+            XliffError::MissingLanguage => write!(f, "no target language in xliff file"),
+            XliffError::InvalidLanguage(lang) => write!(f, "language \"{}\" not in project", lang)
+        }
+    }
+}
+
+impl Error for XliffError {}
+
+impl From<quick_xml::Error> for XliffError {
+    fn from(e: quick_xml::Error) -> Self {
+        XliffError::Xml(e)
+    }
+}
+
+type Result<T> = std::result::Result<T, XliffError>;
+
+/// Translation unit read from file, `state` is taken from `target` in XLIFF 1.2 and from
+/// `segment` in XLIFF 2.0.
+#[derive(Default)]
+struct Unit {
+    id: String,
+    state: Option<String>,
+    target: Option<String>,
+    /// Target contains inline codes without text, which can not be stored in a value.
+    unsupported: bool
+}
+
+/// Imports translations from XLIFF 1.2 or 2.0 file into project. Only targets are imported, in
+/// the file target language, units with `key[category]` ids are plural forms of the key.
+/// Translations in confirmed state are marked as up to date with the source value. Text of inline
+/// elements is kept, units with inline codes which have no text are skipped and reported.
+pub fn import_xliff(data: &str, data_root: &mut DataRootTranslations, project: &Project,
+                    project_chain: &[u16], ignore_unknown: bool) -> Result<ImportResult> {
+    let mut reader = Reader::from_str(data);
+    let mut importer = Importer::new(data_root, project, project_chain, ignore_unknown);
+
+    let mut target_lang: Option<String> = None;
+    let mut unit: Option<Unit> = None;
+    let mut in_target = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                match e.local_name().as_ref() {
+                    b"xliff" => set_lang(&mut target_lang, &e, "trgLang")?,
+                    b"file" => set_lang(&mut target_lang, &e, "target-language")?,
+                    b"trans-unit" | b"unit" => {
+                        check_lang(&target_lang, project)?;
+                        unit = Some(Unit { id: attribute(&e, "id")?.unwrap_or_default(), ..Unit::default() });
+                    }
+                    b"segment" => {
+                        if let Some(unit) = unit.as_mut() {
+                            unit.state = attribute(&e, "state")?;
+                        }
+                    }
+                    b"target" => {
+                        if let Some(unit) = unit.as_mut() {
+                            if let Some(state) = attribute(&e, "state")? {
+                                unit.state = Some(state);
+                            }
+                            unit.target.get_or_insert_with(String::new);
+                            in_target = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Empty(e) if in_target => {
+                if let Some(unit) = unit.as_mut() {
+                    match e.local_name().as_ref() {
+                        b"cp" => match attribute(&e, "hex")?.and_then(|hex| code_point(&hex)) {
+                            Some(c) => unit.target.get_or_insert_with(String::new).push(c),
+                            None => unit.unsupported = true
+                        },
+                        name if MARKERS.iter().any(|m| m.as_bytes() == name) => {}
+                        _ => unit.unsupported = true
+                    }
+                }
+            }
+            Event::Text(text) if in_target => {
+                if let Some(target) = unit.as_mut().and_then(|u| u.target.as_mut()) {
+                    target.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(text) if in_target => {
+                if let Some(target) = unit.as_mut().and_then(|u| u.target.as_mut()) {
+                    target.push_str(&String::from_utf8_lossy(&text));
+                }
+            }
+            Event::End(e) => {
+                match e.local_name().as_ref() {
+                    b"target" => in_target = false,
+                    b"trans-unit" | b"unit" => {
+                        if let (Some(unit), Some(lang)) = (unit.take(), &target_lang) {
+                            import_unit(&mut importer, unit, lang);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(importer.result)
+}
+
+fn import_unit(importer: &mut Importer, unit: Unit, lang: &str) {
+    let target = match unit.target {
+        Some(target) if !target.is_empty() => target,
+        _ => return
+    };
+    let state = unit.state.unwrap_or_default();
+
+    if EMPTY_STATES.contains(&state.as_str()) || unit.id.is_empty() {
+        return;
+    }
+
+    let (key, category) = plural_id(&unit.id);
+
+    if unit.unsupported {
+        if !importer.result.skipped.iter().any(|k| k.eq(key)) {
+            importer.result.skipped.push(key.to_string());
+        }
+        return;
+    }
+
+    match category {
+        Some(category) => importer.plural(key, lang, category, target),
+        None => importer.value(key, lang, target)
+    }
+
+    if CONFIRMED_STATES.contains(&state.as_str()) {
+        importer.confirm(key, lang);
+    }
+}
+
+/// Splits `key[category]` unit id into key and plural category.
+fn plural_id(id: &str) -> (&str, Option<&str>) {
+    let plural = id.strip_suffix(']')
+        .and_then(|id| id.rsplit_once('['))
+        .filter(|(_, category)| PLURAL_CATEGORIES.contains(category));
+
+    match plural {
+        Some((key, category)) => (key, Some(category)),
+        None => (id, None)
+    }
+}
+
+/// Character of XLIFF 2.0 `<cp hex="..."/>` element.
+fn code_point(hex: &str) -> Option<char> {
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+fn set_lang(target_lang: &mut Option<String>, element: &BytesStart, name: &str) -> Result<()> {
+    if let Some(lang) = attribute(element, name)? {
+        *target_lang = Some(lang);
+    }
+
+    Ok(())
+}
+
+fn check_lang(target_lang: &Option<String>, project: &Project) -> Result<()> {
+    match target_lang {
+        Some(lang) if project.langs.contains(lang) => Ok(()),
+        Some(lang) => Err(XliffError::InvalidLanguage(lang.to_string())),
+        None => Err(XliffError::MissingLanguage)
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers for reading XML files with quick-xml.
+
+use quick_xml::events::BytesStart;
+
+/// Unescaped value of the element attribute, `None` when it is not set.
+pub(crate) fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, quick_xml::Error> {
+    match element.try_get_attribute(name)? {
+        Some(attribute) => Ok(Some(attribute.unescape_value()?.to_string())),
+        None => Ok(None)
+    }
+}
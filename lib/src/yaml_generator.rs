@@ -0,0 +1,150 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, LineWriter, Write};
+
+use serde_json::{Map, Value};
+
+use crate::json_generator::insert_nested;
+use crate::placeholders::{argument_name, count_index, replace_placeholders};
+use crate::strings_generator::{Generator, TranslationOut};
+
+/// Words which YAML 1.1 parsers, like the one in Ruby, read as booleans or null.
+const RESERVED_WORDS: [&str; 10] = ["y", "n", "yes", "no", "on", "off", "true", "false", "null", "~"];
+
+/// Rails locale file of a language, dotted keys become nested under the language key. Plural keys
+/// are hashes of plural forms, placeholders are written as `%{arg1}` with the plural count as
+/// `%{count}`.
+pub struct TranslationsYaml {
+    pub lang: String,
+    pub translations: Vec<TranslationOut>
+}
+
+impl Generator for TranslationsYaml {
+    fn generate(&self) -> std::io::Result<()> {
+        let yaml = self.document()?;
+
+        let file = File::create(format!("{}.yml", self.lang))?;
+        let mut file = LineWriter::new(file);
+
+        file.write_all(yaml.as_ref())?;
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+impl TranslationsYaml {
+    fn document(&self) -> std::io::Result<String> {
+        let mut root = Map::new();
+        let mut collisions: Vec<&str> = vec![];
+
+        for translation in self.translations.iter().filter(|t| !t.missing) {
+            if !insert_nested(&mut root, &translation.key, TranslationsYaml::value(translation)) {
+                collisions.push(&translation.key);
+            }
+        }
+
+        if !collisions.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("keys colliding with values of their parent keys in yaml: {}",
+                                          collisions.join(", "))));
+        }
+
+        let mut out = format!("{}:\n", yaml_key(&self.lang));
+        write_map(&mut out, &root, 1);
+        Ok(out)
+    }
+
+    fn value(translation: &TranslationOut) -> Value {
+        if translation.plurals.is_empty() {
+            return Value::String(format(&translation.value, None));
+        }
+
+        let count_index = count_index(&translation.value);
+        let forms = translation.plurals.iter()
+            .map(|(category, value)| (category.to_string(), Value::String(format(value, count_index))))
+            .collect();
+
+        Value::Object(forms)
+    }
+}
+
+fn format(value: &str, count_index: Option<usize>) -> String {
+    replace_placeholders(value, |p| format!("%{{{}}}", argument_name(p, count_index)))
+}
+
+fn write_map(out: &mut String, map: &Map<String, Value>, level: usize) {
+    let indent = "  ".repeat(level);
+
+    for (key, value) in map {
+        match value {
+            Value::Object(children) => {
+                out.push_str(&format!("{}{}:\n", indent, yaml_key(key)));
+                write_map(out, children, level + 1);
+            }
+            Value::String(text) => out.push_str(&format!("{}{}: {}\n", indent, yaml_key(key), yaml_string(text))),
+            _ => {}
+        }
+    }
+}
+
+/// Key as plain scalar when it is safe, quoted otherwise.
+fn yaml_key(key: &str) -> String {
+    let plain = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !RESERVED_WORDS.contains(&key.to_lowercase().as_str());
+
+    if plain { key.to_string() } else { yaml_string(key) }
+}
+
+fn yaml_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strings_generator::TranslationOut;
+    use crate::yaml_generator::TranslationsYaml;
+
+    #[test]
+    fn generate_yaml() {
+        let mut files = TranslationOut::new("app.files", "%d files in %s");
+        files.plurals.insert("one".to_string(), "%d file in %s".to_string());
+        files.plurals.insert("other".to_string(), "%d files in %s".to_string());
+        let mut missing = TranslationOut::new("app.missing", "app.missing");
+        missing.missing = true;
+
+        let generator = TranslationsYaml {
+            lang: "en-US".to_string(),
+            translations: vec![
+                files,
+                TranslationOut::new("app.hello", "Hello \"%s\"\n"),
+                TranslationOut::new("app.no", "No"),
+                missing
+            ]
+        };
+
+        assert_eq!("en-US:\n  \
+                      app:\n    \
+                        files:\n      \
+                          one: \"%{count} file in %{arg2}\"\n      \
+                          other: \"%{count} files in %{arg2}\"\n    \
+                        hello: \"Hello \\\"%{arg1}\\\"\\n\"\n    \
+                        \"no\": \"No\"\n", generator.document().unwrap());
+    }
+
+    #[test]
+    fn report_yaml_collisions() {
+        let generator = TranslationsYaml {
+            lang: "en-US".to_string(),
+            translations: vec![TranslationOut::new("a.b", "Leaf"), TranslationOut::new("a.b.c", "Child")]
+        };
+
+        assert!(generator.document().is_err());
+    }
+}
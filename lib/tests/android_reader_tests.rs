@@ -0,0 +1,90 @@
+mod common;
+
+use std::fs;
+
+use lib::android_reader::{import_android, read_android_files, AndroidFile};
+
+use common::generate_basic_data;
+
+#[test]
+fn import_android_resources() {
+    let mut data_root = generate_basic_data();
+
+    let files = vec![
+        AndroidFile {
+            name: "values-pl/strings.xml".to_string(),
+            lang: "pl-PL".to_string(),
+            data: "<resources>\n    <string name=\"app_hello\">Cześć</string>\n    \
+                   <string name=\"app_id\" translatable=\"false\">identyfikator</string>\n    \
+                   <plurals name=\"app_t1\">\n        <item quantity=\"one\">%1$d plik</item>\n        \
+                   <item quantity=\"few\">%1$d pliki</item>\n        <item quantity=\"other\">%1$d plików</item>\n    \
+                   </plurals>\n    <string-array name=\"app_days\">\n        <item>Pon</item>\n    </string-array>\n\
+                   </resources>".to_string()
+        },
+        AndroidFile {
+            name: "values/strings.xml".to_string(),
+            lang: "en-US".to_string(),
+            data: "<resources>\n    <!-- Greeting -->\n    <string name=\"app_hello\">Hello \\\"%1$s\\\"</string>\n    \
+                   <string name=\"app_id\" translatable=\"false\">id</string>\n    \
+                   <plurals name=\"app_t1\">\n        <item quantity=\"one\">%1$d file</item>\n        \
+                   <item quantity=\"other\">%1$d files</item>\n    </plurals>\n    \
+                   <string-array name=\"app_days\">\n        <item>Mon</item>\n    </string-array>\n\
+                   </resources>".to_string()
+        }
+    ];
+
+    let result = import_android(&files, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app_id".to_string(), "app_days.0".to_string()], result.added);
+    assert_eq!(vec!["app.hello".to_string(), "app.t1".to_string()], result.updated);
+
+    let hello = data_root.translations.get("app.hello").unwrap();
+    assert_eq!("Hello \"%s\"", hello.value(1, "en-US").unwrap());
+    assert_eq!("Cześć", hello.value(1, "pl-PL").unwrap());
+    assert_eq!(Some("Greeting".to_string()), hello.comment);
+
+    let t1 = data_root.translations.get("app.t1").unwrap();
+    assert_eq!("%d files", t1.value(1, "en-US").unwrap());
+    assert_eq!("%d plików", t1.value(1, "pl-PL").unwrap());
+
+    assert_eq!("id", data_root.translations.get("app_id").unwrap().value(1, "en-US").unwrap());
+    assert!(data_root.translations.get("app_id").unwrap().value(1, "pl-PL").is_none());
+    assert_eq!("Pon", data_root.translations.get("app_days.0").unwrap().value(1, "pl-PL").unwrap());
+}
+
+#[test]
+fn import_android_ignoring_unknown_keys() {
+    let mut data_root = generate_basic_data();
+    let files = vec![AndroidFile {
+        name: "values-pl/strings.xml".to_string(),
+        lang: "pl-PL".to_string(),
+        data: "<resources><string name=\"app_hello\">Cześć</string><string name=\"app_new\">Nowy</string></resources>"
+            .to_string()
+    }];
+
+    let result = import_android(&files, &mut data_root.translations, &data_root.projects[0], &[1], true).unwrap();
+
+    assert_eq!(vec!["app_new".to_string()], result.ignored);
+    assert!(!data_root.translations.contains_key("app_new"));
+}
+
+#[test]
+fn read_res_tree() {
+    let root = std::env::temp_dir().join(format!("stringc_res_{}", std::process::id()));
+    for dir in &["values", "values-pl-rPL", "values-fr", "values-night", "layout"] {
+        fs::create_dir_all(root.join("res").join(dir)).unwrap();
+    }
+
+    fs::write(root.join("res/values/strings.xml"), "<resources/>").unwrap();
+    fs::write(root.join("res/values-pl-rPL/strings.xml"), "<resources>pl</resources>").unwrap();
+    fs::write(root.join("res/values-fr/strings.xml"), "<resources>fr</resources>").unwrap();
+    fs::write(root.join("res/values-night/strings.xml"), "<resources>night</resources>").unwrap();
+    fs::write(root.join("res/layout/main.xml"), "<LinearLayout/>").unwrap();
+
+    let data_root = generate_basic_data();
+    let files = read_android_files(&root, &data_root.projects[0]).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let files: Vec<(&str, &str)> = files.iter().map(|f| (f.lang.as_str(), f.data.as_str())).collect();
+    assert_eq!(vec![("en-US", "<resources/>"), ("pl-PL", "<resources>pl</resources>")], files);
+}
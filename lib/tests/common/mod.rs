@@ -0,0 +1,6 @@
+use lib::json_data::{DataRoot, get_projects};
+
+/// Two project fixture, its JSON is shared with the unit tests in `src`.
+pub fn generate_basic_data() -> DataRoot {
+    get_projects(include_str!("basic_data.json")).expect("Error parsing json")
+}
@@ -1,9 +1,16 @@
-use std::collections::BTreeMap;
+#![allow(clippy::bool_assert_comparison)]
 
-use lib::excel_file::{EFile, ExcelFile};
-use lib::excel_reader::{import_excel, InvalidLanguageError};
-use lib::json_data::{DataRoot, get_projects, Project, Translation};
-use std::panic::resume_unwind;
+mod common;
+
+use std::fs;
+
+use lib::excel_file::{CsvFile, EFile, ExcelFile};
+use lib::excel_reader::import_excel;
+use lib::excel_writer::prepare_translations;
+use lib::json_data::{find_project, inherited_project, project_chain};
+use lib::project_manager::derive_project;
+
+use common::generate_basic_data;
 
 struct ExcelFileMock {
     columns: Vec<String>,
@@ -33,7 +40,7 @@ fn import_excel_wrong_language_project1_file() {
 
     let mut data_root = generate_basic_data();
     let project = &data_root.projects[0];
-    let result = import_excel(&mut file, &mut data_root.translations, project, false);
+    let result = import_excel(&mut file, &mut data_root.translations, project, &[1], false);
 
     assert_eq!(result.is_err(), true);
 }
@@ -50,7 +57,7 @@ fn import_excel_project2_file() {
     };
 
     let mut data_root = generate_basic_data();
-    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], false);
+    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], &[2], false);
 
     assert!(result.is_ok());
     let result = result.unwrap();
@@ -70,7 +77,7 @@ fn import_empty_excel_file() {
     let mut file = ExcelFileMock { rows: vec![], columns: vec![] };
     let mut data_root = generate_basic_data();
 
-    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[0], false);
+    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[0], &[1], false);
 
     assert!(result.is_ok());
     let result = result.unwrap();
@@ -94,7 +101,7 @@ fn import_ignore_unknown_excel_file() {
     };
     let mut data_root = generate_basic_data();
 
-    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], true);
+    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], &[2], true);
 
     assert!(result.is_ok());
     let result = result.unwrap();
@@ -120,7 +127,7 @@ fn import_excel_inverted_languages_file() {
     };
 
     let mut data_root = generate_basic_data();
-    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], false);
+    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], &[2], false);
 
     assert!(result.is_ok());
     let result = result.unwrap();
@@ -135,86 +142,155 @@ fn import_excel_inverted_languages_file() {
     assert_eq!("updated1-de", data_root.translations.get("app.t2").unwrap().values.get(&2).unwrap().get("de-DE").unwrap());
 }
 
-fn generate_basic_data() -> DataRoot {
-    let json = r#"
-    {
-      "projects": [
-        {
-          "id": 1,
-          "name": "TestProject1",
-          "langs": [
-            "en-US",
-            "pl-PL"
-          ],
-          "defaultLang": "en-US"
-        },
-        {
-          "id": 2,
-          "name": "TestProject2",
-          "langs": [
-            "en-US",
-            "de-DE"
-          ],
-          "defaultLang": "en-US"
-        }
-      ],
-      "translations": {
-        "app.hello": {
-          "projects": [
-            1,
-            2
-          ],
-          "values": {
-            "1": {
-              "en-US": "Hello",
-              "pl-PL": "Witaj"
-            },
-            "2": {
-              "en-US": "Hello",
-              "de-DE": "Hallo"
-            }
-          }
-        },
-        "app.t1": {
-          "projects": [
-            1,
-            2
-          ],
-          "values": {
-            "1": {
-              "en-US": "test1-en-p1",
-              "pl-PL": "test1-pl-p1"
-            },
-            "2": {
-              "en-US": "test1-en-proj2",
-              "de-DE": "test1-de-proj2"
-            }
-          }
-        },
-        "app.t2": {
-          "projects": [
-            2
-          ],
-          "values": {
-            "2": {
-              "en-US": "test2-en-proj2",
-              "de-DE": "test2-de-proj2"
-            }
-          }
-        },
-        "app.t3": {
-          "projects": [
-            1
-          ],
-          "values": {
-            "2": {
-              "en-US": "test3-en-proj1"
-            }
-          }
-        }
-      }
-    }
-    "#;
+#[test]
+fn import_changed_source_marks_translations_outdated() {
+    let mut file = ExcelFileMock {
+        rows: vec![
+            vec!["key".to_string(), "de-DE".to_string(), "en-US".to_string()],
+            vec!["app.hello".to_string(), "Hallo".to_string(), "Hello!".to_string()],
+            vec!["app.t1".to_string(), "test1-de-new".to_string(), "test1-en-new".to_string()]
+        ],
+        columns: vec![],
+    };
+
+    let mut data_root = generate_basic_data();
+    let project = &data_root.projects[1];
+    let result = import_excel(&mut file, &mut data_root.translations, project, &[2], false);
+
+    assert!(result.is_ok());
+    assert_eq!(vec!["de-DE".to_string()], data_root.translations.get("app.hello").unwrap().outdated_langs(project, &[2]));
+    assert!(data_root.translations.get("app.t1").unwrap().outdated_langs(project, &[2]).is_empty());
+    assert!(data_root.translations.get("app.t1").unwrap().outdated_langs(&data_root.projects[0], &[1]).is_empty());
+}
+
+#[test]
+fn import_unchanged_values_into_derived_project() {
+    let mut file = ExcelFileMock {
+        rows: vec![
+            vec!["key".to_string(), "en-US".to_string(), "pl-PL".to_string()],
+            vec!["app.hello".to_string(), "Hello".to_string(), "Witaj".to_string()],
+            vec!["app.t1".to_string(), "test1-en-p1".to_string(), "Test".to_string()]
+        ],
+        columns: vec![],
+    };
+
+    let mut data_root = generate_basic_data();
+    derive_project(&mut data_root, "Brand", "TestProject1").unwrap();
+    let brand = find_project(&data_root.projects, "Brand").unwrap();
+    let chain = project_chain(&data_root.projects, brand);
+    let project = inherited_project(&data_root.projects, brand);
+
+    import_excel(&mut file, &mut data_root.translations, &project, &chain, false).unwrap();
+
+    let hello = data_root.translations.get("app.hello").unwrap();
+    assert!(!hello.values.contains_key(&3));
+    assert!(!hello.projects.contains(&3));
+
+    let t1 = data_root.translations.get("app.t1").unwrap();
+    assert_eq!(Some(&"Test".to_string()), t1.value(3, "pl-PL"));
+    assert!(t1.value(3, "en-US").is_none());
+    assert!(!t1.projects.contains(&3));
+}
+
+#[test]
+fn import_csv_project2_file() {
+    let data = "\u{feff}key,en-US,de-DE\r\nnew1,\"added, \"\"quoted\"\"\",\"line1\nline2\"\r\napp.t2,updated1-en\r\n";
+    let mut file = CsvFile::parse(data.as_bytes(), b',').unwrap();
+
+    let mut data_root = generate_basic_data();
+    let result = import_excel(&mut file, &mut data_root.translations, &data_root.projects[1], &[2], false).unwrap();
+
+    assert_eq!(vec!["new1".to_string()], result.added);
+    assert_eq!(vec!["app.t2".to_string()], result.updated);
+    let new1 = data_root.translations.get("new1").unwrap();
+    assert_eq!("added, \"quoted\"", new1.value(2, "en-US").unwrap());
+    assert_eq!("line1\nline2", new1.value(2, "de-DE").unwrap());
+}
 
-    get_projects(json).expect("Error parsing json")
-}
\ No newline at end of file
+#[test]
+fn import_utf16_tsv_file() {
+    let data: Vec<u8> = [0xFF, 0xFE].iter().copied()
+        .chain("key\ten-US\tpl-PL\napp.hello\tHello\tCześć\n".encode_utf16().flat_map(|u| u.to_le_bytes()))
+        .collect();
+    let mut file = CsvFile::parse(&data, b'\t').unwrap();
+
+    assert_eq!(vec!["key".to_string(), "en-US".to_string(), "pl-PL".to_string()], file.columns());
+
+    let mut data_root = generate_basic_data();
+    import_excel(&mut file, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!("Cześć", data_root.translations.get("app.hello").unwrap().value(1, "pl-PL").unwrap());
+}
+
+#[test]
+fn export_csv_file_round_trip() {
+    let file_name = std::env::temp_dir().join(format!("stringc_export_{}.csv", std::process::id()));
+    let file_name = file_name.to_str().unwrap();
+
+    let mut data_root = generate_basic_data();
+    data_root.translations.get_mut("app.t1").unwrap().values.get_mut(&1).unwrap()
+        .insert("pl-PL".to_string(), "test1, \"pl\"".to_string());
+    prepare_translations(&data_root, &data_root.projects[0], false).generate_csv(file_name, b',').unwrap();
+
+    let data = fs::read(file_name).unwrap();
+    let mut file = CsvFile::new(file_name).unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(data.starts_with("\u{feff}Key,en-US,pl-PL\n".as_bytes()));
+    assert!(file.rows().contains(&vec!["app.t1".to_string(), "test1-en-p1".to_string(), "test1, \"pl\"".to_string()]));
+}
+
+#[test]
+fn export_ods_file_round_trip() {
+    // Extension does not match the content, the format is detected from the file itself.
+    let file_name = std::env::temp_dir().join(format!("stringc_export_{}.xlsx", std::process::id()));
+    let file_name = file_name.to_str().unwrap();
+
+    let mut data_root = generate_basic_data();
+    data_root.translations.get_mut("app.t1").unwrap().values.get_mut(&1).unwrap()
+        .insert("pl-PL".to_string(), "  a  <b>\tc &\nd ".to_string());
+    prepare_translations(&data_root, &data_root.projects[0], false).generate_ods(file_name).unwrap();
+
+    let mut file = ExcelFile::new(file_name).unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let rows = file.rows();
+    assert_eq!(vec!["Key".to_string(), "en-US".to_string(), "pl-PL".to_string()], rows[0]);
+    assert!(rows.contains(&vec!["app.t1".to_string(), "test1-en-p1".to_string(), "  a  <b>\tc &\nd ".to_string()]));
+    assert!(rows.contains(&vec!["app.hello".to_string(), "Hello".to_string(), "Witaj".to_string()]));
+}
+
+#[test]
+fn export_shared_values_once() {
+    let file_name = std::env::temp_dir().join(format!("stringc_shared_{}.csv", std::process::id()));
+    let file_name = file_name.to_str().unwrap();
+
+    let mut data_root = generate_basic_data();
+    let projects = data_root.projects.clone();
+    data_root.translations.get_mut("app.hello").unwrap().set_shared_value(&projects, "pl-PL", "Witaj".to_string());
+    prepare_translations(&data_root, &data_root.projects[0], false).generate_csv(file_name, b',').unwrap();
+
+    let mut file = CsvFile::new(file_name).unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let rows = file.rows();
+    assert_eq!(vec!["Key", "en-US", "pl-PL", "pl-PL (shared)"], rows[0]);
+    assert!(rows.contains(&vec!["app.hello".to_string(), "Hello".to_string(), "".to_string(), "Witaj".to_string()]));
+    assert!(rows.contains(&vec!["app.t1".to_string(), "test1-en-p1".to_string(), "test1-pl-p1".to_string(), "".to_string()]));
+
+    let result = import_excel(&mut file, &mut data_root.translations, &projects[0], &[1], false).unwrap();
+    assert!(result.skipped.is_empty());
+    assert!(data_root.translations.get("app.hello").unwrap().is_shared(1, "pl-PL"));
+
+    let mut file = ExcelFileMock {
+        columns: vec![],
+        rows: vec![
+            vec!["key".to_string(), "pl-PL".to_string(), "pl-PL (shared)".to_string()],
+            vec!["app.hello".to_string(), "".to_string(), "Cześć".to_string()]
+        ]
+    };
+    let result = import_excel(&mut file, &mut data_root.translations, &projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.hello".to_string()], result.skipped);
+    assert_eq!("Witaj", data_root.translations.get("app.hello").unwrap().resolve(1, "pl-PL").unwrap());
+}
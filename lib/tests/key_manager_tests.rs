@@ -0,0 +1,92 @@
+mod common;
+
+use lib::key_manager::{add_key, delete_key, get_key, rename_key, set_key_value, set_shared_key_value};
+use lib::project_manager::derive_project;
+
+use common::generate_basic_data;
+
+#[test]
+fn add_key_to_projects() {
+    let mut data_root = generate_basic_data();
+
+    let result = add_key(&mut data_root, "app.new", &["TestProject1", "TestProject2"], Some("New"));
+
+    assert!(result.is_ok());
+    let translation = data_root.translations.get("app.new").unwrap();
+    assert_eq!(vec![1, 2], translation.projects);
+    assert_eq!("New", translation.value(1, "en-US").unwrap());
+    assert_eq!("New", translation.value(2, "en-US").unwrap());
+}
+
+#[test]
+fn add_existing_key_to_other_project() {
+    let mut data_root = generate_basic_data();
+
+    assert!(add_key(&mut data_root, "app.t2", &["TestProject2"], None).is_err());
+    assert!(add_key(&mut data_root, "app.t2", &["TestProject1", "TestProject2"], None).is_ok());
+    assert_eq!(vec![2, 1], data_root.translations.get("app.t2").unwrap().projects);
+    assert_eq!(4, data_root.translations.len());
+}
+
+#[test]
+fn add_key_to_derived_project() {
+    let mut data_root = generate_basic_data();
+    derive_project(&mut data_root, "Brand", "TestProject1").unwrap();
+
+    assert!(add_key(&mut data_root, "app.hello", &["Brand"], None).is_err());
+    assert!(add_key(&mut data_root, "app.new", &["TestProject1", "Brand"], Some("New")).is_ok());
+    assert!(add_key(&mut data_root, "app.brand", &["Brand"], Some("Brand")).is_ok());
+
+    let translation = data_root.translations.get("app.new").unwrap();
+    assert_eq!(vec![1], translation.projects);
+    assert!(translation.value(3, "en-US").is_none());
+    assert_eq!("Brand", data_root.translations.get("app.brand").unwrap().value(3, "en-US").unwrap());
+}
+
+#[test]
+fn add_key_to_unknown_project() {
+    let mut data_root = generate_basic_data();
+
+    assert!(add_key(&mut data_root, "app.new", &["Unknown"], None).is_err());
+    assert!(!data_root.translations.contains_key("app.new"));
+}
+
+#[test]
+fn set_and_get_key_value() {
+    let mut data_root = generate_basic_data();
+
+    assert!(set_key_value(&mut data_root, "app.hello", "TestProject1", "pl-PL", "Cześć").is_ok());
+    assert!(set_key_value(&mut data_root, "app.hello", "TestProject1", "de-DE", "Hallo").is_err());
+    assert!(set_key_value(&mut data_root, "app.unknown", "TestProject1", "pl-PL", "Cześć").is_err());
+
+    let translation = get_key(&data_root, "app.hello").unwrap();
+    assert_eq!("Cześć", translation.value(1, "pl-PL").unwrap());
+    assert_eq!("Hallo", translation.value(2, "de-DE").unwrap());
+}
+
+#[test]
+fn set_shared_key_value_for_projects() {
+    let mut data_root = generate_basic_data();
+
+    assert!(set_shared_key_value(&mut data_root, "app.hello", "fr-FR", "Bonjour").is_err());
+    assert!(set_shared_key_value(&mut data_root, "app.hello", "en-US", "Hello").is_ok());
+
+    let translation = get_key(&data_root, "app.hello").unwrap();
+    assert!(translation.is_shared(1, "en-US"));
+    assert!(translation.is_shared(2, "en-US"));
+    assert_eq!("Witaj", translation.resolve(1, "pl-PL").unwrap());
+}
+
+#[test]
+fn rename_and_delete_key() {
+    let mut data_root = generate_basic_data();
+
+    assert!(rename_key(&mut data_root, "app.hello", "app.t1").is_err());
+    assert!(rename_key(&mut data_root, "app.hello", "app.greeting").is_ok());
+    assert!(!data_root.translations.contains_key("app.hello"));
+    assert_eq!("Hallo", data_root.translations.get("app.greeting").unwrap().value(2, "de-DE").unwrap());
+
+    assert!(delete_key(&mut data_root, "app.greeting").is_ok());
+    assert!(delete_key(&mut data_root, "app.greeting").is_err());
+    assert_eq!(3, data_root.translations.len());
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use lib::excel_file::EFile;
+use lib::excel_reader::import_excel;
+use lib::po_reader::import_po;
+
+use common::generate_basic_data;
+
+const PO_PL: &str = r#"msgid ""
+msgstr ""
+"Project-Id-Version: TestProject1\n"
+"Language: pl_PL\n"
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+#. Greeting
+msgctxt "app.hello"
+msgid "Hello"
+msgstr ""
+"Cześć, \"świecie\"\n"
+"!"
+
+#, fuzzy
+msgctxt "app.t1"
+msgid "test1-en-p1"
+msgstr "fuzzy-pl"
+
+msgctxt "app.files"
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] "%d plik"
+msgstr[1] "%d pliki"
+msgstr[2] "%d plików"
+
+msgid "app.t3"
+msgstr "test3-pl"
+"#;
+
+struct ExcelFileMock {
+    rows: Vec<Vec<String>>
+}
+
+impl EFile for ExcelFileMock {
+    fn rows(&mut self) -> Vec<Vec<String>> {
+        self.rows.clone()
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+#[test]
+fn import_po_file() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_po(PO_PL, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.files".to_string()], result.added);
+    assert_eq!(vec!["app.hello".to_string(), "app.t3".to_string()], result.updated);
+
+    let translations = &data_root.translations;
+    assert_eq!("Cześć, \"świecie\"\n!", translations.get("app.hello").unwrap().value(1, "pl-PL").unwrap());
+    assert_eq!("test3-pl", translations.get("app.t3").unwrap().value(1, "pl-PL").unwrap());
+    assert_ne!("fuzzy-pl", translations.get("app.t1").unwrap().value(1, "pl-PL").unwrap());
+
+    let plurals = translations.get("app.files").unwrap().plurals_in(&[1], "pl-PL").unwrap();
+    assert_eq!("%d plik", plurals.get("one").unwrap());
+    assert_eq!("%d pliki", plurals.get("few").unwrap());
+    assert_eq!("%d plików", plurals.get("many").unwrap());
+    assert_eq!("%d plików", plurals.get("other").unwrap());
+    assert_eq!("%d plików", translations.get("app.files").unwrap().value(1, "pl-PL").unwrap());
+}
+
+#[test]
+fn import_po_confirms_outdated_translation() {
+    let mut file = ExcelFileMock {
+        rows: vec![
+            vec!["key".to_string(), "en-US".to_string()],
+            vec!["app.hello".to_string(), "Hello!".to_string()]
+        ]
+    };
+    let po = "msgid \"\"\nmsgstr \"Language: pl_PL\\n\"\n\nmsgctxt \"app.hello\"\nmsgid \"Hello!\"\nmsgstr \"Witaj\"\n";
+
+    let mut data_root = generate_basic_data();
+    let project = &data_root.projects[0];
+    assert!(import_excel(&mut file, &mut data_root.translations, project, &[1], false).is_ok());
+    assert_eq!(vec!["pl-PL".to_string()], data_root.translations.get("app.hello").unwrap().outdated_langs(project, &[1]));
+    assert!(import_po(po, &mut data_root.translations, project, &[1], false).is_ok());
+
+    assert_eq!("Witaj", data_root.translations.get("app.hello").unwrap().value(1, "pl-PL").unwrap());
+    assert!(data_root.translations.get("app.hello").unwrap().outdated_langs(project, &[1]).is_empty());
+}
+
+#[test]
+fn import_invalid_po_file() {
+    let mut data_root = generate_basic_data();
+    let project = &data_root.projects[1];
+
+    assert!(import_po(PO_PL, &mut data_root.translations, project, &[2], false).is_err());
+    assert!(import_po("msgid \"\"\nmsgstr \"\"\n", &mut data_root.translations, project, &[2], false).is_err());
+    assert!(import_po("msgid \"\"\nmsgstr \"Language: de_DE\\n\"\nmsgid unquoted\n", &mut data_root.translations,
+                      project, &[2], false).is_err());
+}
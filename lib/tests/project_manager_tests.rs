@@ -0,0 +1,120 @@
+mod common;
+
+use lib::json_data::{find_project, inherited_project, project_chain};
+use lib::key_manager::set_key_value;
+use lib::project_manager::{add_lang, add_project, clone_project, derive_project, remove_lang, remove_project,
+                           rename_project, set_default_lang};
+
+use common::generate_basic_data;
+
+#[test]
+fn add_and_rename_project() {
+    let mut data_root = generate_basic_data();
+
+    assert!(add_project(&mut data_root, "TestProject2", "en-US", &[]).is_err());
+    assert!(add_project(&mut data_root, "TestProject3", "fr-FR", &["en-US", "fr-FR"]).is_ok());
+    assert!(rename_project(&mut data_root, "TestProject3", "TestProject1").is_err());
+    assert!(rename_project(&mut data_root, "TestProject3", "French").is_ok());
+
+    let project = find_project(&data_root.projects, "French").unwrap();
+    assert_eq!(3, project.id);
+    assert_eq!(vec!["fr-FR".to_string(), "en-US".to_string()], project.langs);
+    assert_eq!("fr-FR", project.default_lang);
+}
+
+#[test]
+fn clone_project_copies_values() {
+    let mut data_root = generate_basic_data();
+
+    assert!(clone_project(&mut data_root, "TestProject2", "TestProject2Clone").is_ok());
+
+    let project = find_project(&data_root.projects, "TestProject2Clone").unwrap();
+    assert_eq!(3, project.id);
+    assert_eq!(vec!["en-US".to_string(), "de-DE".to_string()], project.langs);
+    assert_eq!(vec![1, 2, 3], data_root.translations.get("app.hello").unwrap().projects);
+    assert_eq!("test2-de-proj2", data_root.translations.get("app.t2").unwrap().value(3, "de-DE").unwrap());
+    assert!(!data_root.translations.get("app.t3").unwrap().projects.contains(&3));
+}
+
+#[test]
+fn derive_project_inherits_from_parent() {
+    let mut data_root = generate_basic_data();
+
+    assert!(derive_project(&mut data_root, "Brand", "TestProject1").is_ok());
+    assert!(add_lang(&mut data_root, "Brand", "pl-PL").is_err());
+    assert!(add_lang(&mut data_root, "Brand", "fr-FR").is_ok());
+    assert!(remove_project(&mut data_root, "TestProject1").is_err());
+
+    let project = find_project(&data_root.projects, "Brand").unwrap();
+    assert_eq!(Some(1), project.parent);
+    assert_eq!(vec![3, 1], project_chain(&data_root.projects, project));
+
+    let project = inherited_project(&data_root.projects, project);
+    assert_eq!(vec!["en-US".to_string(), "pl-PL".to_string(), "fr-FR".to_string()], project.langs);
+    assert_eq!("en-US", project.default_lang);
+}
+
+#[test]
+fn remove_project_cleans_values() {
+    let mut data_root = generate_basic_data();
+
+    let removed = remove_project(&mut data_root, "TestProject2").unwrap();
+
+    assert_eq!(vec!["app.t2".to_string()], removed);
+    assert_eq!(1, data_root.projects.len());
+    assert_eq!(3, data_root.translations.len());
+    assert!(!data_root.translations.get("app.hello").unwrap().values.contains_key(&2));
+    assert_eq!(vec![1], data_root.translations.get("app.t1").unwrap().projects);
+}
+
+#[test]
+fn add_and_remove_lang() {
+    let mut data_root = generate_basic_data();
+
+    assert!(add_lang(&mut data_root, "TestProject1", "pl-PL").is_err());
+    assert!(add_lang(&mut data_root, "TestProject1", "de-DE").is_ok());
+    assert!(remove_lang(&mut data_root, "TestProject1", "en-US").is_err());
+    assert!(remove_lang(&mut data_root, "TestProject1", "pl-PL").is_ok());
+
+    let project = find_project(&data_root.projects, "TestProject1").unwrap();
+    assert_eq!(vec!["en-US".to_string(), "de-DE".to_string()], project.langs);
+    assert!(data_root.translations.get("app.hello").unwrap().value(1, "pl-PL").is_none());
+    assert_eq!("Hallo", data_root.translations.get("app.hello").unwrap().value(2, "de-DE").unwrap());
+}
+
+#[test]
+fn remove_lang_from_parent_project() {
+    let mut data_root = generate_basic_data();
+    derive_project(&mut data_root, "Brand", "TestProject1").unwrap();
+    assert!(set_key_value(&mut data_root, "app.hello", "Brand", "pl-PL", "Cześć").is_ok());
+    assert!(data_root.translations.get("app.hello").unwrap().value(3, "pl-PL").is_some());
+
+    assert!(remove_lang(&mut data_root, "Brand", "pl-PL").is_err());
+    assert!(remove_lang(&mut data_root, "Brand", "de-DE").is_err());
+    assert!(remove_lang(&mut data_root, "TestProject1", "pl-PL").is_ok());
+
+    let hello = data_root.translations.get("app.hello").unwrap();
+    assert!(hello.value(1, "pl-PL").is_none());
+    assert!(hello.value(3, "pl-PL").is_none());
+}
+
+#[test]
+fn add_project_without_free_id() {
+    let mut data_root = generate_basic_data();
+    data_root.projects[1].id = u16::MAX;
+
+    assert!(add_project(&mut data_root, "TestProject3", "en-US", &[]).is_err());
+    assert_eq!(2, data_root.projects.len());
+}
+
+#[test]
+fn change_default_lang() {
+    let mut data_root = generate_basic_data();
+
+    assert!(set_default_lang(&mut data_root, "TestProject1", "de-DE").is_err());
+    assert!(set_default_lang(&mut data_root, "TestProject1", "pl-PL").is_ok());
+
+    let project = find_project(&data_root.projects, "TestProject1").unwrap();
+    assert_eq!("pl-PL", project.default_lang);
+    assert!(data_root.translations.get("app.hello").unwrap().outdated_langs(project, &[1]).is_empty());
+}
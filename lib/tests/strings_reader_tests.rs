@@ -0,0 +1,126 @@
+mod common;
+
+use std::fs;
+
+use lib::json_data::{find_project, inherited_project, project_chain};
+use lib::project_manager::derive_project;
+use lib::strings_reader::{import_strings, read_strings_files, StringsFile};
+
+use common::generate_basic_data;
+
+#[test]
+fn import_strings_files() {
+    let mut data_root = generate_basic_data();
+    data_root.translations.get_mut("app.t1").unwrap().values.get_mut(&1).unwrap()
+        .insert("en-US".to_string(), "%d files in %s".to_string());
+
+    let files = vec![
+        StringsFile {
+            name: "pl.lproj/Localizable.strings".to_string(),
+            lang: "pl-PL".to_string(),
+            data: "/* Plik */\n\"app.t1\" = \"%@ plików w %@\";\n\"app.new\" = \"Nowy\";".to_string()
+        },
+        StringsFile {
+            name: "en.lproj/Localizable.strings".to_string(),
+            lang: "en-US".to_string(),
+            data: "/* Greeting */\n\"app.hello\" = \"Hello \\\"%@\\\"\";\n\
+                   /* No comment provided by engineer. */\n\"app.t1\" = \"%@ files in %@\";".to_string()
+        }
+    ];
+
+    let result = import_strings(&files, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.new".to_string()], result.added);
+    assert_eq!(vec!["app.hello".to_string(), "app.t1".to_string()], result.updated);
+
+    let hello = data_root.translations.get("app.hello").unwrap();
+    assert_eq!("Hello \"%s\"", hello.value(1, "en-US").unwrap());
+    assert_eq!(Some("Greeting".to_string()), hello.comment);
+
+    let t1 = data_root.translations.get("app.t1").unwrap();
+    assert_eq!("%d files in %s", t1.value(1, "en-US").unwrap());
+    assert_eq!("%d plików w %s", t1.value(1, "pl-PL").unwrap());
+    assert!(t1.comment.is_none());
+    assert!(!t1.is_outdated(&data_root.projects[0], &[1], "pl-PL"));
+
+    assert_eq!("Nowy", data_root.translations.get("app.new").unwrap().value(1, "pl-PL").unwrap());
+}
+
+#[test]
+fn import_strings_comments() {
+    let mut data_root = generate_basic_data();
+    let files = vec![StringsFile {
+        name: "en.lproj/Localizable.strings".to_string(),
+        lang: "en-US".to_string(),
+        data: "/* Localizable.strings */\n\n\"app.hello\" = \"Hello\";\n\"app.t1\" = \"T1\"; // Trailing\n\
+               \"app.t3\" = \"T3\";\n\n/* Title */\n\"app.new\" = \"New\";".to_string()
+    }];
+
+    import_strings(&files, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    let translations = &data_root.translations;
+    assert!(translations.get("app.hello").unwrap().comment.is_none());
+    assert!(translations.get("app.t1").unwrap().comment.is_none());
+    assert!(translations.get("app.t3").unwrap().comment.is_none());
+    assert_eq!(Some("Title".to_string()), translations.get("app.new").unwrap().comment);
+}
+
+#[test]
+fn import_strings_into_derived_project() {
+    let mut data_root = generate_basic_data();
+    data_root.translations.get_mut("app.t1").unwrap().values.get_mut(&1).unwrap()
+        .insert("en-US".to_string(), "%d files".to_string());
+    derive_project(&mut data_root, "Brand", "TestProject1").unwrap();
+    let brand = find_project(&data_root.projects, "Brand").unwrap();
+    let chain = project_chain(&data_root.projects, brand);
+    let project = inherited_project(&data_root.projects, brand);
+    let files = vec![StringsFile {
+        name: "pl.lproj/Localizable.strings".to_string(),
+        lang: "pl-PL".to_string(),
+        data: "\"app.t1\" = \"%@ plików\";".to_string()
+    }];
+
+    import_strings(&files, &mut data_root.translations, &project, &chain, false).unwrap();
+
+    assert_eq!("%d plików", data_root.translations.get("app.t1").unwrap().value(3, "pl-PL").unwrap());
+}
+
+#[test]
+fn import_strings_ignoring_unknown_keys() {
+    let mut data_root = generate_basic_data();
+    let files = vec![StringsFile {
+        name: "Localizable.strings".to_string(),
+        lang: "pl-PL".to_string(),
+        data: "\"app.hello\" = \"Cześć\";\n\"app.new\" = \"Nowy\";".to_string()
+    }];
+
+    let result = import_strings(&files, &mut data_root.translations, &data_root.projects[0], &[1], true).unwrap();
+
+    assert_eq!(vec!["app.new".to_string()], result.ignored);
+    assert!(!data_root.translations.contains_key("app.new"));
+}
+
+#[test]
+fn read_lproj_tree() {
+    let root = std::env::temp_dir().join(format!("stringc_lproj_{}", std::process::id()));
+    for dir in &["Base.lproj", "pl.lproj", "fr.lproj", "Other"] {
+        fs::create_dir_all(root.join("App").join(dir)).unwrap();
+    }
+
+    let utf16: Vec<u8> = [0xFF, 0xFE].iter().copied()
+        .chain("\"app.hello\" = \"Cześć\";".encode_utf16().flat_map(|u| u.to_le_bytes()))
+        .collect();
+    fs::write(root.join("App/pl.lproj/Localizable.strings"), utf16).unwrap();
+    fs::write(root.join("App/Base.lproj/Localizable.strings"), "\"app.hello\" = \"Hi\";").unwrap();
+    fs::write(root.join("App/fr.lproj/Localizable.strings"), "\"app.hello\" = \"Salut\";").unwrap();
+    fs::write(root.join("App/Other/Localizable.strings"), "\"app.hello\" = \"Other\";").unwrap();
+    fs::write(root.join("App/Other/Localized_pl-PL.strings"), "\"app.t1\" = \"Eksport\";").unwrap();
+
+    let data_root = generate_basic_data();
+    let files = read_strings_files(&root, &data_root.projects[0]).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let files: Vec<(&str, &str)> = files.iter().map(|f| (f.lang.as_str(), f.data.as_str())).collect();
+    assert_eq!(vec![("en-US", "\"app.hello\" = \"Hi\";"), ("pl-PL", "\"app.t1\" = \"Eksport\";"),
+                    ("pl-PL", "\"app.hello\" = \"Cześć\";")], files);
+}
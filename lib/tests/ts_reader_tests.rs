@@ -0,0 +1,85 @@
+mod common;
+
+use lib::json_data::{find_project, inherited_project, project_chain};
+use lib::project_manager::derive_project;
+use lib::ts_reader::import_ts;
+
+use common::generate_basic_data;
+
+const TS_PL: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE TS>
+<TS version="2.1" language="pl_PL" sourcelanguage="en_US">
+<context>
+    <name>app</name>
+    <message id="app.hello">
+        <source>Hello</source>
+        <translation>Cześć &amp; witaj</translation>
+    </message>
+    <message id="app.t1">
+        <source>test1-en-p1</source>
+        <translation type="unfinished">niegotowe</translation>
+    </message>
+    <message id="app.files" numerus="yes">
+        <source>%n files in %2</source>
+        <translation>
+            <numerusform>%n plik w %2</numerusform>
+            <numerusform>%n pliki w %2</numerusform>
+            <numerusform>%n plików w %2</numerusform>
+        </translation>
+    </message>
+</context>
+</TS>
+"#;
+
+#[test]
+fn import_ts_file() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_ts(TS_PL, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.files".to_string()], result.added);
+    assert_eq!(vec!["app.hello".to_string()], result.updated);
+
+    let translations = &data_root.translations;
+    assert_eq!("Cześć & witaj", translations.get("app.hello").unwrap().value(1, "pl-PL").unwrap());
+    assert_ne!("niegotowe", translations.get("app.t1").unwrap().value(1, "pl-PL").unwrap());
+
+    let plurals = translations.get("app.files").unwrap().plurals_in(&[1], "pl-PL").unwrap();
+    assert_eq!("%d plik w %s", plurals.get("one").unwrap());
+    assert_eq!("%d pliki w %s", plurals.get("few").unwrap());
+    assert_eq!("%d plików w %s", plurals.get("many").unwrap());
+}
+
+#[test]
+fn import_ts_ignore_unknown() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_ts(TS_PL, &mut data_root.translations, &data_root.projects[0], &[1], true).unwrap();
+
+    assert_eq!(vec!["app.files".to_string()], result.ignored);
+    assert!(!data_root.translations.contains_key("app.files"));
+}
+
+#[test]
+fn import_ts_into_derived_project() {
+    let mut data_root = generate_basic_data();
+    data_root.translations.get_mut("app.hello").unwrap().values.get_mut(&1).unwrap()
+        .insert("en-US".to_string(), "Hello %d".to_string());
+    derive_project(&mut data_root, "Brand", "TestProject1").unwrap();
+    let brand = find_project(&data_root.projects, "Brand").unwrap();
+    let chain = project_chain(&data_root.projects, brand);
+    let project = inherited_project(&data_root.projects, brand);
+    let ts = "<TS language=\"pl_PL\"><context><message id=\"app.hello\"><source>Hello %1</source>\
+              <translation>Cześć %1</translation></message></context></TS>";
+
+    import_ts(ts, &mut data_root.translations, &project, &chain, false).unwrap();
+
+    assert_eq!("Cześć %d", data_root.translations.get("app.hello").unwrap().value(3, "pl-PL").unwrap());
+}
+
+#[test]
+fn import_ts_wrong_language() {
+    let mut data_root = generate_basic_data();
+
+    assert!(import_ts(TS_PL, &mut data_root.translations, &data_root.projects[1], &[2], false).is_err());
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use lib::xcstrings_reader::import_xcstrings;
+
+use common::generate_basic_data;
+
+const CATALOG: &str = r#"
+{
+  "sourceLanguage" : "en-US",
+  "strings" : {
+    "app.files" : {
+      "comment" : "Number of files",
+      "localizations" : {
+        "en-US" : {
+          "variations" : {
+            "plural" : {
+              "one" : { "stringUnit" : { "state" : "translated", "value" : "%d file" } },
+              "other" : { "stringUnit" : { "state" : "translated", "value" : "%d files" } }
+            }
+          }
+        }
+      }
+    },
+    "app.hello" : {
+      "localizations" : {
+        "en-US" : { "stringUnit" : { "state" : "translated", "value" : "Hello %@" } },
+        "fr-FR" : { "stringUnit" : { "state" : "translated", "value" : "Bonjour %@" } },
+        "pl-PL" : { "stringUnit" : { "state" : "translated", "value" : "Witaj %1$@ (%lld)" } }
+      }
+    }
+  },
+  "version" : "1.0"
+}
+"#;
+
+#[test]
+fn import_catalog() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_xcstrings(CATALOG, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.files".to_string()], result.added);
+    assert_eq!(vec!["app.hello".to_string()], result.updated);
+
+    let hello = data_root.translations.get("app.hello").unwrap();
+    assert_eq!("Hello %s", hello.value(1, "en-US").unwrap());
+    assert_eq!("Witaj %1$s (%d)", hello.value(1, "pl-PL").unwrap());
+    assert!(hello.value(1, "fr-FR").is_none());
+
+    let files = data_root.translations.get("app.files").unwrap();
+    assert_eq!(Some("Number of files".to_string()), files.comment);
+    assert_eq!("%d files", files.value(1, "en-US").unwrap());
+    assert_eq!("%d file", files.plurals_in(&[1], "en-US").unwrap().get("one").unwrap());
+}
+
+#[test]
+fn import_catalog_keeps_stored_percent() {
+    let mut data_root = generate_basic_data();
+    let project = &data_root.projects[0];
+    let hello = data_root.translations.get_mut("app.hello").unwrap();
+    hello.set_value(project, &[1], "en-US", "50% off".to_string());
+    hello.set_value(project, &[1], "pl-PL", "50% taniej".to_string());
+    let catalog = r#"{ "sourceLanguage" : "en-US", "strings" : { "app.hello" : { "localizations" : {
+        "en-US" : { "stringUnit" : { "state" : "translated", "value" : "50%% off" } } } } }, "version" : "1.0" }"#;
+
+    import_xcstrings(catalog, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    let hello = data_root.translations.get("app.hello").unwrap();
+    assert_eq!("50% off", hello.value(1, "en-US").unwrap());
+    assert!(!hello.is_outdated(&data_root.projects[0], &[1], "pl-PL"));
+}
+
+#[test]
+fn import_catalog_ignore_unknown() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_xcstrings(CATALOG, &mut data_root.translations, &data_root.projects[0], &[1], true).unwrap();
+
+    assert_eq!(vec!["app.files".to_string()], result.ignored);
+    assert!(!data_root.translations.contains_key("app.files"));
+}
+
+#[test]
+fn import_invalid_catalog() {
+    let mut data_root = generate_basic_data();
+
+    assert!(import_xcstrings("{}", &mut data_root.translations, &data_root.projects[0], &[1], false).is_err());
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use lib::excel_reader::import_excel;
+use lib::excel_file::EFile;
+use lib::xliff_reader::import_xliff;
+
+use common::generate_basic_data;
+
+const XLIFF_V12: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+  <file original="TestProject1" source-language="en-US" target-language="pl-PL" datatype="plaintext">
+    <body>
+      <trans-unit id="app.hello">
+        <source>Hello</source>
+        <target state="translated">Cześć &amp; witaj</target>
+      </trans-unit>
+      <trans-unit id="app.t1">
+        <source>test1-en-p1</source>
+        <target state="new"></target>
+      </trans-unit>
+      <trans-unit id="app.new">
+        <source>New</source>
+        <target state="needs-review-translation">Nowy</target>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>
+"#;
+
+const XLIFF_V20: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff version="2.0" xmlns="urn:oasis:names:tc:xliff:document:2.0" srcLang="en-US" trgLang="de-DE">
+  <file id="f1">
+    <unit id="app.hello">
+      <segment state="reviewed">
+        <source>Hello</source>
+        <target>Hallo</target>
+      </segment>
+    </unit>
+    <unit id="app.t2">
+      <segment state="initial">
+        <source>test2-en-proj2</source>
+        <target>test2-de-proj2</target>
+      </segment>
+    </unit>
+  </file>
+</xliff>
+"#;
+
+struct ExcelFileMock {
+    rows: Vec<Vec<String>>
+}
+
+impl EFile for ExcelFileMock {
+    fn rows(&mut self) -> Vec<Vec<String>> {
+        self.rows.clone()
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+#[test]
+fn import_xliff_v12() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_xliff(XLIFF_V12, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.new".to_string()], result.added);
+    assert_eq!(vec!["app.hello".to_string()], result.updated);
+    assert_eq!("Cześć & witaj", data_root.translations.get("app.hello").unwrap().value(1, "pl-PL").unwrap());
+    assert_eq!("Nowy", data_root.translations.get("app.new").unwrap().value(1, "pl-PL").unwrap());
+    assert!(data_root.translations.get("app.t1").unwrap().value(1, "pl-PL").is_some());
+}
+
+#[test]
+fn import_xliff_ignore_unknown() {
+    let mut data_root = generate_basic_data();
+
+    let result = import_xliff(XLIFF_V12, &mut data_root.translations, &data_root.projects[0], &[1], true).unwrap();
+
+    assert_eq!(vec!["app.new".to_string()], result.ignored);
+    assert!(!data_root.translations.contains_key("app.new"));
+}
+
+#[test]
+fn import_xliff_v20_confirms_outdated_translation() {
+    let mut file = ExcelFileMock {
+        rows: vec![
+            vec!["key".to_string(), "en-US".to_string()],
+            vec!["app.hello".to_string(), "Hello!".to_string()]
+        ]
+    };
+
+    let mut data_root = generate_basic_data();
+    let project = &data_root.projects[1];
+    assert!(import_excel(&mut file, &mut data_root.translations, project, &[2], false).is_ok());
+    assert_eq!(vec!["de-DE".to_string()], data_root.translations.get("app.hello").unwrap().outdated_langs(project, &[2]));
+
+    let result = import_xliff(XLIFF_V20, &mut data_root.translations, project, &[2], false).unwrap();
+
+    assert_eq!(vec!["app.hello".to_string()], result.updated);
+    assert!(data_root.translations.get("app.hello").unwrap().outdated_langs(project, &[2]).is_empty());
+}
+
+#[test]
+fn import_xliff_plurals_and_inline_elements() {
+    let data = r#"<xliff version="2.0" srcLang="en-US" trgLang="pl-PL">
+  <file id="f1">
+    <unit id="app.t1[one]">
+      <segment state="translated">
+        <source>test1-en-p1</source>
+        <target>Plik <pc id="1">jeden</pc><cp hex="21"/></target>
+      </segment>
+    </unit>
+    <unit id="app.t1[few]">
+      <segment state="translated">
+        <source>test1-en-p1</source>
+        <target>Pliki</target>
+      </segment>
+    </unit>
+    <unit id="app.hello">
+      <segment state="translated">
+        <source>Hello</source>
+        <target>Cześć <ph id="1"/></target>
+      </segment>
+    </unit>
+  </file>
+</xliff>"#;
+
+    let mut data_root = generate_basic_data();
+
+    let result = import_xliff(data, &mut data_root.translations, &data_root.projects[0], &[1], false).unwrap();
+
+    assert_eq!(vec!["app.t1".to_string()], result.updated);
+    assert_eq!(vec!["app.hello".to_string()], result.skipped);
+    assert!(!data_root.translations.contains_key("app.t1[one]"));
+
+    let plurals = data_root.translations.get("app.t1").unwrap().plurals_in(&[1], "pl-PL").unwrap();
+    assert_eq!("Plik jeden!", plurals.get("one").unwrap());
+    assert_eq!("Pliki", plurals.get("few").unwrap());
+    assert_ne!("Cześć ", data_root.translations.get("app.hello").unwrap().value(1, "pl-PL").unwrap());
+}
+
+#[test]
+fn import_xliff_wrong_language() {
+    let mut data_root = generate_basic_data();
+
+    assert!(import_xliff(XLIFF_V20, &mut data_root.translations, &data_root.projects[0], &[1], false).is_err());
+    assert!(import_xliff("<xliff><file><unit id=\"a\"></unit></file></xliff>", &mut data_root.translations,
+                         &data_root.projects[0], &[1], false).is_err());
+}
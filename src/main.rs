@@ -1,29 +1,70 @@
-use std::collections::BTreeMap;
 use std::env;
+use std::error::Error;
 use std::fs;
+use std::path::Path;
 use std::process;
 
-use clap::{App, Arg, ArgMatches};
+use clap::{App, AppSettings, Arg, ArgMatches};
 
-use lib::json_data::{DataRoot, generate_template, get_projects, save};
+use lib::json_data::{DataRoot, DataRootTranslations, Project, generate_template, get_projects,
+                     inherited_project, project_chain, save};
+use lib::key_manager::{add_key, delete_key, get_key, rename_key, set_key_value, set_shared_key_value};
+use lib::project_manager::{add_lang, add_project, clone_project, derive_project, remove_lang, remove_project,
+                           rename_project, set_default_lang};
 
-use lib::excel_writer::{ExcelTranslations};
+use lib::excel_writer::prepare_translations;
 use lib::strings_generator::{generate_strings};
-use lib::excel_file::ExcelFile;
-use lib::excel_reader::import_excel;
-use lib::strings_generator::StringsGeneratorType::{Ios, Android};
+use lib::excel_file::{CsvFile, ExcelFile, csv_delimiter};
+use lib::excel_reader::{ImportResult, import_excel};
+use lib::xcstrings_reader::import_xcstrings;
+use lib::xliff_generator::XliffVersion;
+use lib::xliff_reader::import_xliff;
+use lib::po_reader::import_po;
+use lib::ts_reader::import_ts;
+use lib::strings_reader::{import_strings, read_strings_files};
+use lib::android_reader::{import_android, read_android_files};
+use lib::strings_generator::StringsGeneratorType::{Ios, Android, XcStrings, Xliff, Po, Mo, Arb, Json,
+                                                Properties, Resx, Ts, Yaml, WebExt, Fluent,
+                                                TypeScript, Rust};
 
 
 const COMMAND_GENERATE_TEMPLATE: &str = "template";
 const COMMAND_IMPORT_XLSX: &str = "import";
 const COMMAND_EXPORT_XLSX: &str = "export-xlsx";
 const COMMAND_EXPORT_STRINGS: &str = "export";
+const COMMAND_KEY: &str = "key";
+const COMMAND_KEY_ADD: &str = "add";
+const COMMAND_KEY_SET: &str = "set";
+const COMMAND_KEY_SHARE: &str = "share";
+const COMMAND_KEY_GET: &str = "get";
+const COMMAND_KEY_RENAME: &str = "rename";
+const COMMAND_KEY_DELETE: &str = "delete";
+const COMMAND_PROJECT: &str = "project";
+const COMMAND_PROJECT_ADD: &str = "add";
+const COMMAND_PROJECT_RENAME: &str = "rename";
+const COMMAND_PROJECT_CLONE: &str = "clone";
+const COMMAND_PROJECT_DERIVE: &str = "derive";
+const COMMAND_PROJECT_REMOVE: &str = "remove";
+const COMMAND_PROJECT_DEFAULT_LANG: &str = "default-lang";
+const COMMAND_LANG: &str = "lang";
+const COMMAND_LANG_ADD: &str = "add";
+const COMMAND_LANG_REMOVE: &str = "remove";
 const ARG_FILE_NAME: &str = "file_name";
 const ARG_INPUT_FILE_NAME: &str = "input_file_name";
 const ARG_IMPORT_IGNORE_UNKNOWN_KEYS: &str = "ignore_unknown_keys";
 const ARG_OUTPUT_FILE_NAME: &str = "export_file_name";
 const ARG_PROJECT_NAME: &str = "project_name";
 const ARG_EXPORT_STRINGS_TYPE: &str = "export_type";
+const ARG_EXPORT_OUTDATED_ONLY: &str = "outdated_only";
+const ARG_EXPORT_ANDROID_PACKAGE: &str = "android_package";
+const ARG_KEY: &str = "key";
+const ARG_NEW_KEY: &str = "new_key";
+const ARG_PROJECT_NAMES: &str = "project_names";
+const ARG_LANG: &str = "lang";
+const ARG_VALUE: &str = "value";
+const ARG_NEW_PROJECT_NAME: &str = "new_project_name";
+const ARG_LANGS: &str = "langs";
+const ARG_PARENT_PROJECT_NAME: &str = "parent_project_name";
 
 fn main() {
     let matches = get_arguments();
@@ -33,6 +74,9 @@ fn main() {
         Some(COMMAND_IMPORT_XLSX) => import_xlsx_command(&matches),
         Some(COMMAND_EXPORT_XLSX) => export_xlsx_command(&matches),
         Some(COMMAND_EXPORT_STRINGS) => export_strings(&matches),
+        Some(COMMAND_KEY) => key_command(&matches),
+        Some(COMMAND_PROJECT) => project_command(&matches),
+        Some(COMMAND_LANG) => lang_command(&matches),
         _ => {
             println!("error: No command provided");
             process::exit(1);
@@ -73,23 +117,71 @@ fn import_xlsx_command(matches: &ArgMatches) {
         .is_present(ARG_IMPORT_IGNORE_UNKNOWN_KEYS);
 
     let mut projects_data = get_data(file_name);
-    let mut file = ExcelFile::new(xlsx_file_name)
+
+    let project = projects_data.projects.iter().find(|&p| {
+        p.name.eq(project_name)
+    }).expect("Invalid project name");
+
+    let chain = project_chain(&projects_data.projects, project);
+    let project = inherited_project(&projects_data.projects, project);
+
+    let result = import_file(xlsx_file_name, &mut projects_data.translations, &project, &chain, ignore_unknown)
         .unwrap_or_else(|err| {
             println!("error: {}", err);
             process::exit(1)
         });
 
-    let project = projects_data.projects.iter().find(|&p| {
-        p.name.eq(project_name)
-    }).expect("Invalid project name");
+    println!("Added: {}, updated: {}, ignored: {}", result.added.len(), result.updated.len(), result.ignored.len());
+    for key in &result.skipped {
+        println!("warning: \"{}\" skipped, unsupported markup or edited shared value", key);
+    }
 
-    let result = import_excel(&mut file, &mut projects_data.translations, &project, ignore_unknown);
     if let Err(e) = save(file_name, &projects_data) {
         println!("error: {}", e);
         process::exit(1)
     };
 }
 
+/// Imports file in format detected from its extension, other files are read as xlsx, xls, xlsb or
+/// ods workbooks. Directories are searched for Android `values` resources, or iOS `.lproj` strings
+/// files when there are none.
+fn import_file(input_file_name: &str, translations: &mut DataRootTranslations, project: &Project, chain: &[u16],
+               ignore_unknown: bool) -> Result<ImportResult, Box<dyn Error>> {
+    let path = Path::new(input_file_name);
+    if path.is_dir() {
+        let android_files = read_android_files(path, project)?;
+        if !android_files.is_empty() {
+            return Ok(import_android(&android_files, translations, project, chain, ignore_unknown)?);
+        }
+        return Ok(import_strings(&read_strings_files(path, project)?, translations, project, chain, ignore_unknown)?);
+    }
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let result = match extension.as_str() {
+        "xcstrings" => import_xcstrings(&load_data_file(input_file_name), translations, project, chain, ignore_unknown)?,
+        "xlf" | "xliff" => import_xliff(&load_data_file(input_file_name), translations, project, chain, ignore_unknown)?,
+        "po" => import_po(&load_data_file(input_file_name), translations, project, chain, ignore_unknown)?,
+        "ts" => import_ts(&load_data_file(input_file_name), translations, project, chain, ignore_unknown)?,
+        "strings" => import_strings(&read_strings_files(path, project)?, translations, project, chain, ignore_unknown)?,
+        "xml" => import_android(&read_android_files(path, project)?, translations, project, chain, ignore_unknown)?,
+        "csv" | "tsv" | "tab" => {
+            let mut file = CsvFile::new(input_file_name)?;
+            import_excel(&mut file, translations, project, chain, ignore_unknown)?
+        }
+        _ => {
+            let mut file = ExcelFile::new(input_file_name)?;
+            import_excel(&mut file, translations, project, chain, ignore_unknown)?
+        }
+    };
+
+    Ok(result)
+}
+
 fn export_xlsx_command(matches: &ArgMatches) {
     let command = matches.subcommand_matches(COMMAND_EXPORT_XLSX)
         .unwrap();
@@ -106,26 +198,35 @@ fn export_xlsx_command(matches: &ArgMatches) {
         .value_of(ARG_PROJECT_NAME)
         .unwrap();
 
+    let outdated_only = command
+        .is_present(ARG_EXPORT_OUTDATED_ONLY);
+
     let data = get_data(file_name);
 
     let project = data.projects.iter().find(|&p| {
         p.name.eq(project_name)
     }).expect("Invalid project name");
 
-    let da: BTreeMap<_, _> = data.translations.into_iter()
-        .filter(|(_, t)| t.projects.contains(&project.id))
-        .collect();
+    let excel_translations = prepare_translations(&data, project, outdated_only);
+    let extension = Path::new(xlsx_file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
 
-    for (key, value) in da {
-        if let Some(asd) = value.values.get(&project.id) {
-            for lang in project.langs.to_vec() {
-
-            }
+    if ["csv", "tsv", "tab"].contains(&extension.as_str()) {
+        if let Err(e) = excel_translations.generate_csv(xlsx_file_name, csv_delimiter(xlsx_file_name)) {
+            println!("error: {}", e);
+            process::exit(1)
         }
+    } else if extension.eq("ods") {
+        if let Err(e) = excel_translations.generate_ods(xlsx_file_name) {
+            println!("error: {}", e);
+            process::exit(1)
+        }
+    } else {
+        excel_translations.generate(xlsx_file_name);
     }
-
-    let excel_translations = ExcelTranslations::new(project.langs.to_vec(), vec![]);
-    excel_translations.generate(xlsx_file_name);
 }
 
 fn export_strings(matches: &ArgMatches) {
@@ -144,6 +245,10 @@ fn export_strings(matches: &ArgMatches) {
         .values_of(ARG_EXPORT_STRINGS_TYPE)
         .unwrap();
 
+    let android_package = command
+        .value_of(ARG_EXPORT_ANDROID_PACKAGE)
+        .map(|p| p.to_string());
+
     let projects_data = get_data(file_name);
 
     let project = projects_data.projects.iter().find(|&p| {
@@ -151,12 +256,213 @@ fn export_strings(matches: &ArgMatches) {
     }).expect("Invalid project name");
 
     for export_type in export_types {
-        match export_type {
-            "ios" => generate_strings(Ios, &projects_data.translations, &project),
-            "and" => generate_strings(Android, &projects_data.translations, &project),
-            _ => {  }
+        let result = match export_type {
+            "ios" => generate_strings(Ios, &projects_data, project),
+            "and" => generate_strings(Android { package: android_package.clone() }, &projects_data, project),
+            "xcstrings" => generate_strings(XcStrings, &projects_data, project),
+            "xliff" => generate_strings(Xliff(XliffVersion::V12), &projects_data, project),
+            "xliff2" => generate_strings(Xliff(XliffVersion::V20), &projects_data, project),
+            "po" => generate_strings(Po, &projects_data, project),
+            "mo" => generate_strings(Mo, &projects_data, project),
+            "arb" => generate_strings(Arb, &projects_data, project),
+            "json" => generate_strings(Json { nested: true }, &projects_data, project),
+            "json-flat" => generate_strings(Json { nested: false }, &projects_data, project),
+            "properties" => generate_strings(Properties { utf8: false }, &projects_data, project),
+            "properties-utf8" => generate_strings(Properties { utf8: true }, &projects_data, project),
+            "resx" => generate_strings(Resx, &projects_data, project),
+            "ts" => generate_strings(Ts, &projects_data, project),
+            "yaml" => generate_strings(Yaml, &projects_data, project),
+            "webext" => generate_strings(WebExt, &projects_data, project),
+            "ftl" => generate_strings(Fluent, &projects_data, project),
+            "dts" => generate_strings(TypeScript, &projects_data, project),
+            "rust" => generate_strings(Rust, &projects_data, project),
+            _ => Ok(())
+        };
+
+        if let Err(e) = result {
+            println!("error: {}", e);
+            process::exit(1)
+        }
+    }
+}
+
+fn key_command(matches: &ArgMatches) {
+    let (name, command) = matches.subcommand_matches(COMMAND_KEY)
+        .unwrap()
+        .subcommand()
+        .unwrap();
+
+    let file_name = command
+        .value_of(ARG_FILE_NAME)
+        .unwrap();
+
+    let key = command
+        .value_of(ARG_KEY)
+        .unwrap();
+
+    let mut projects_data = get_data(file_name);
+
+    let result = match name {
+        COMMAND_KEY_ADD => {
+            let project_names: Vec<&str> = command.values_of(ARG_PROJECT_NAMES)
+                .unwrap()
+                .collect();
+            add_key(&mut projects_data, key, &project_names, command.value_of(ARG_VALUE))
+        }
+        COMMAND_KEY_SET => set_key_value(&mut projects_data, key,
+                                         command.value_of(ARG_PROJECT_NAME).unwrap(),
+                                         command.value_of(ARG_LANG).unwrap(),
+                                         command.value_of(ARG_VALUE).unwrap()),
+        COMMAND_KEY_SHARE => set_shared_key_value(&mut projects_data, key,
+                                                  command.value_of(ARG_LANG).unwrap(),
+                                                  command.value_of(ARG_VALUE).unwrap()),
+        COMMAND_KEY_RENAME => rename_key(&mut projects_data, key, command.value_of(ARG_NEW_KEY).unwrap()),
+        COMMAND_KEY_DELETE => delete_key(&mut projects_data, key).map(|_| ()),
+        _ => {
+            print_key(&projects_data, key);
+            return;
+        }
+    };
+
+    if let Err(e) = result {
+        println!("error: {}", e);
+        process::exit(1)
+    }
+
+    if let Err(e) = save(file_name, &projects_data) {
+        println!("error: {}", e);
+        process::exit(1)
+    };
+}
+
+fn print_key(projects_data: &DataRoot, key: &str) {
+    let translation = get_key(projects_data, key)
+        .unwrap_or_else(|err| {
+            println!("error: {}", err);
+            process::exit(1)
+        });
+
+    println!("{}", key);
+    if !translation.shared.is_empty() {
+        println!("  Shared");
+
+        for (lang, value) in &translation.shared {
+            println!("    {}: {}", lang, value);
+        }
+    }
+
+    for project in &projects_data.projects {
+        let chain = project_chain(&projects_data.projects, project);
+        if !translation.in_projects(&chain) {
+            continue;
+        }
+
+        let project = inherited_project(&projects_data.projects, project);
+        println!("  {}", project.name);
+
+        for lang in &project.langs {
+            let value = translation.resolve_in(&chain, lang)
+                .map(|v| v.as_str())
+                .unwrap_or("");
+            let status = if translation.value(project.id, lang).is_some() {
+                ""
+            } else if chain.iter().skip(1).any(|&id| translation.value(id, lang).is_some()) {
+                " (inherited)"
+            } else if translation.shared.contains_key(lang) {
+                " (shared)"
+            } else {
+                ""
+            };
+            let outdated = if translation.is_outdated(&project, &chain, lang) { " (outdated)" } else { "" };
+
+            println!("    {}: {}{}{}", lang, value, status, outdated);
+        }
+    }
+}
+
+fn project_command(matches: &ArgMatches) {
+    let (name, command) = matches.subcommand_matches(COMMAND_PROJECT)
+        .unwrap()
+        .subcommand()
+        .unwrap();
+
+    let file_name = command
+        .value_of(ARG_FILE_NAME)
+        .unwrap();
+
+    let project_name = command
+        .value_of(ARG_PROJECT_NAME)
+        .unwrap();
+
+    let mut projects_data = get_data(file_name);
+
+    let result = match name {
+        COMMAND_PROJECT_ADD => {
+            let langs: Vec<&str> = command.values_of(ARG_LANGS)
+                .map(|langs| langs.collect())
+                .unwrap_or_default();
+            add_project(&mut projects_data, project_name, command.value_of(ARG_LANG).unwrap(), &langs)
         }
+        COMMAND_PROJECT_RENAME => rename_project(&mut projects_data, project_name,
+                                                 command.value_of(ARG_NEW_PROJECT_NAME).unwrap()),
+        COMMAND_PROJECT_CLONE => clone_project(&mut projects_data, project_name,
+                                               command.value_of(ARG_NEW_PROJECT_NAME).unwrap()),
+        COMMAND_PROJECT_DERIVE => derive_project(&mut projects_data, project_name,
+                                                 command.value_of(ARG_PARENT_PROJECT_NAME).unwrap()),
+        COMMAND_PROJECT_REMOVE => remove_project(&mut projects_data, project_name)
+            .map(|removed_keys| {
+                for key in removed_keys {
+                    println!("Removing key: {}", key);
+                }
+            }),
+        _ => set_default_lang(&mut projects_data, project_name, command.value_of(ARG_LANG).unwrap())
+    };
+
+    if let Err(e) = result {
+        println!("error: {}", e);
+        process::exit(1)
+    }
+
+    if let Err(e) = save(file_name, &projects_data) {
+        println!("error: {}", e);
+        process::exit(1)
+    };
+}
+
+fn lang_command(matches: &ArgMatches) {
+    let (name, command) = matches.subcommand_matches(COMMAND_LANG)
+        .unwrap()
+        .subcommand()
+        .unwrap();
+
+    let file_name = command
+        .value_of(ARG_FILE_NAME)
+        .unwrap();
+
+    let project_name = command
+        .value_of(ARG_PROJECT_NAME)
+        .unwrap();
+
+    let lang = command
+        .value_of(ARG_LANG)
+        .unwrap();
+
+    let mut projects_data = get_data(file_name);
+
+    let result = match name {
+        COMMAND_LANG_ADD => add_lang(&mut projects_data, project_name, lang),
+        _ => remove_lang(&mut projects_data, project_name, lang)
+    };
+
+    if let Err(e) = result {
+        println!("error: {}", e);
+        process::exit(1)
     }
+
+    if let Err(e) = save(file_name, &projects_data) {
+        println!("error: {}", e);
+        process::exit(1)
+    };
 }
 
 fn get_arguments() -> ArgMatches {
@@ -173,7 +479,7 @@ fn get_arguments() -> ArgMatches {
             )
         )
         .subcommand(App::new(COMMAND_IMPORT_XLSX)
-            .about("Imports data from xlsx file")
+            .about("Imports data from xlsx, xls, xlsb, ods, csv, tsv, xcstrings, xliff, po, ts, iOS strings or Android resources file, or lproj or res directory")
             .arg(Arg::new(ARG_FILE_NAME)
                 .required(true)
                 .takes_value(false)
@@ -182,7 +488,7 @@ fn get_arguments() -> ArgMatches {
             .arg(Arg::new(ARG_INPUT_FILE_NAME)
                 .required(true)
                 .takes_value(false)
-                .about("File to import")
+                .about("File or directory to import")
             )
             .arg(Arg::new(ARG_PROJECT_NAME)
                 .required(true)
@@ -198,7 +504,7 @@ fn get_arguments() -> ArgMatches {
             )
         )
         .subcommand(App::new(COMMAND_EXPORT_XLSX)
-            .about("Exports data to xlsx file")
+            .about("Exports data to xlsx, ods, csv or tsv file")
             .arg(Arg::new(ARG_FILE_NAME)
                 .required(true)
                 .takes_value(false)
@@ -207,16 +513,23 @@ fn get_arguments() -> ArgMatches {
             .arg(Arg::new(ARG_OUTPUT_FILE_NAME)
                 .required(true)
                 .takes_value(false)
-                .about("File name to export xlsx")
+                .about("File name to export, ods, csv and tsv extensions select the format")
             )
             .arg(Arg::new(ARG_PROJECT_NAME)
                 .required(true)
                 .takes_value(false)
                 .about("Project to export")
             )
+            .arg(Arg::new(ARG_EXPORT_OUTDATED_ONLY)
+                .required(false)
+                .takes_value(false)
+                .about("Exports only keys with translations outdated by source changes")
+                .short('u')
+                .long("outdated")
+            )
         )
         .subcommand(App::new(COMMAND_EXPORT_STRINGS)
-            .about("Exports strings for iOS and/or Android targets")
+            .about("Exports strings for iOS (strings with Swift accessors, xcstrings), Android (strings.xml with Kotlin accessors), Flutter (arb), web (json, yaml, dts types), browser extensions (webext), Java (properties), .NET (resx), Qt (ts), Fluent (ftl), Rust (rust), gettext (po, mo) or translation vendors (xliff)")
             .arg(Arg::new(ARG_FILE_NAME)
                 .required(true)
                 .takes_value(false)
@@ -231,7 +544,291 @@ fn get_arguments() -> ArgMatches {
                 .required(true)
                 .min_values(1)
                 .multiple_values(true)
-                .possible_values(["and", "ios"].as_ref())
+                .possible_values(["and", "ios", "xcstrings", "xliff", "xliff2", "po", "mo", "arb", "json", "json-flat",
+                                  "properties", "properties-utf8", "resx", "ts", "yaml", "webext", "ftl", "dts", "rust"].as_ref())
+            )
+            .arg(Arg::new(ARG_EXPORT_ANDROID_PACKAGE)
+                .required(false)
+                .takes_value(true)
+                .about("Package of the Android app R class, generates Kotlin accessors for Android strings")
+                .long("package")
+            )
+        )
+        .subcommand(App::new(COMMAND_KEY)
+            .about("Manages translation keys")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(App::new(COMMAND_KEY_ADD)
+                .about("Adds key to one or more projects")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Key name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAMES)
+                    .required(true)
+                    .min_values(1)
+                    .multiple_values(true)
+                    .about("Projects to add key to")
+                )
+                .arg(Arg::new(ARG_VALUE)
+                    .required(false)
+                    .takes_value(true)
+                    .about("Default language value")
+                    .long("value")
+                )
+            )
+            .subcommand(App::new(COMMAND_KEY_SET)
+                .about("Sets key value for project and language")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Key name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_LANG)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Language")
+                )
+                .arg(Arg::new(ARG_VALUE)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Value")
+                )
+            )
+            .subcommand(App::new(COMMAND_KEY_SHARE)
+                .about("Sets key value shared by all its projects")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Key name")
+                )
+                .arg(Arg::new(ARG_LANG)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Language")
+                )
+                .arg(Arg::new(ARG_VALUE)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Value")
+                )
+            )
+            .subcommand(App::new(COMMAND_KEY_GET)
+                .about("Prints key values")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Key name")
+                )
+            )
+            .subcommand(App::new(COMMAND_KEY_RENAME)
+                .about("Renames key in all projects")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Key name")
+                )
+                .arg(Arg::new(ARG_NEW_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("New key name")
+                )
+            )
+            .subcommand(App::new(COMMAND_KEY_DELETE)
+                .about("Deletes key from all projects")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_KEY)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Key name")
+                )
+            )
+        )
+        .subcommand(App::new(COMMAND_PROJECT)
+            .about("Manages projects")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(App::new(COMMAND_PROJECT_ADD)
+                .about("Adds new project")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_LANG)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Default language")
+                )
+                .arg(Arg::new(ARG_LANGS)
+                    .required(false)
+                    .min_values(0)
+                    .multiple_values(true)
+                    .about("Other project languages")
+                )
+            )
+            .subcommand(App::new(COMMAND_PROJECT_RENAME)
+                .about("Renames project")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_NEW_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("New project name")
+                )
+            )
+            .subcommand(App::new(COMMAND_PROJECT_CLONE)
+                .about("Creates copy of project with all its keys and values")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_NEW_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("New project name")
+                )
+            )
+            .subcommand(App::new(COMMAND_PROJECT_DERIVE)
+                .about("Adds project inheriting keys, languages and values from parent project")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_PARENT_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Parent project name")
+                )
+            )
+            .subcommand(App::new(COMMAND_PROJECT_REMOVE)
+                .about("Removes project with its values")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+            )
+            .subcommand(App::new(COMMAND_PROJECT_DEFAULT_LANG)
+                .about("Changes project default language")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_LANG)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Language")
+                )
+            )
+        )
+        .subcommand(App::new(COMMAND_LANG)
+            .about("Manages project languages")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(App::new(COMMAND_LANG_ADD)
+                .about("Adds language to project")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_LANG)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Language")
+                )
+            )
+            .subcommand(App::new(COMMAND_LANG_REMOVE)
+                .about("Removes language with its values from project")
+                .arg(Arg::new(ARG_FILE_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Data file name")
+                )
+                .arg(Arg::new(ARG_PROJECT_NAME)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Project name")
+                )
+                .arg(Arg::new(ARG_LANG)
+                    .required(true)
+                    .takes_value(false)
+                    .about("Language")
+                )
             )
         )
         .get_matches();